@@ -0,0 +1,28 @@
+//! Pit the built-in strategies against each other.
+//!
+//! Usage: `cargo run --release --example arena [games] [seed]`
+use judgment::GameConfig;
+use judgment_bots::{Arena, HeuristicStrategy, MonteCarloStrategy, RandomStrategy, Strategy};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let games = args.next().map_or(100, |games| games.parse().unwrap());
+    let seed = args.next().map_or(0, |seed| seed.parse().unwrap());
+
+    let names = ["random", "heuristic", "monte carlo"];
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![
+        Box::new(RandomStrategy::new(seed)),
+        Box::new(HeuristicStrategy),
+        Box::new(MonteCarloStrategy::new(32, seed)),
+    ];
+    let arena = Arena::new(GameConfig::builder(3, 8).build().unwrap());
+    let report = arena.run(&mut strategies, games, seed).unwrap();
+    println!("{:<12} {:>8} {:>10}", "strategy", "win rate", "avg score");
+    for ((name, win_rate), score) in names
+        .iter()
+        .zip(report.win_rates())
+        .zip(report.average_scores())
+    {
+        println!("{name:<12} {:>7.1}% {score:>10.2}", win_rate * 100.0);
+    }
+}
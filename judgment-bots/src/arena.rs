@@ -0,0 +1,182 @@
+use judgment::{GameConfig, InvalidTransition, Judgment, Phase, Transition};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::Strategy;
+
+/// Plays games between strategies and keeps score.
+///
+/// # Examples
+/// ```
+/// use judgment::GameConfig;
+/// use judgment_bots::{Arena, HeuristicStrategy, RandomStrategy, Strategy};
+///
+/// let arena = Arena::new(GameConfig::builder(2, 3).build().unwrap());
+/// let mut strategies: Vec<Box<dyn Strategy>> = vec![
+///     Box::new(RandomStrategy::new(1)),
+///     Box::new(HeuristicStrategy),
+/// ];
+/// let report = arena.run(&mut strategies, 10, 7).unwrap();
+/// assert_eq!(report.games, 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Arena {
+    config: GameConfig,
+}
+
+/// What happened over the games of an [`Arena::run`], indexed like the
+/// strategies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaReport {
+    pub games: usize,
+    /// Games won by each strategy. A win shared between tied players is
+    /// split evenly between them.
+    pub wins: Vec<f64>,
+    /// Sum of the final scores of each strategy over all games.
+    pub total_scores: Vec<i64>,
+}
+
+impl ArenaReport {
+    /// Fraction of the games won by each strategy.
+    pub fn win_rates(&self) -> Vec<f64> {
+        self.wins
+            .iter()
+            .map(|wins| wins / self.games as f64)
+            .collect()
+    }
+
+    /// Average final score of each strategy.
+    pub fn average_scores(&self) -> Vec<f64> {
+        self.total_scores
+            .iter()
+            .map(|score| *score as f64 / self.games as f64)
+            .collect()
+    }
+}
+
+impl Arena {
+    /// An arena for games played with the rules in `config`.
+    pub fn new(config: GameConfig) -> Self {
+        Arena { config }
+    }
+
+    /// Play `games` games between `strategies`, one per seat, with deals
+    /// seeded from `seed`.
+    ///
+    /// The strategies move one seat to the left every game, so that none of
+    /// them keeps the advantage or disadvantage of a particular seat.
+    ///
+    /// # Panics
+    /// Panics if there is not exactly one strategy per player of the config.
+    ///
+    /// # Errors
+    /// Errors if a strategy makes an illegal move.
+    pub fn run(
+        &self,
+        strategies: &mut [Box<dyn Strategy>],
+        games: usize,
+        seed: u64,
+    ) -> Result<ArenaReport, InvalidTransition> {
+        let players = usize::from(self.config.players());
+        assert_eq!(
+            strategies.len(),
+            players,
+            "every seat needs exactly one strategy"
+        );
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let mut report = ArenaReport {
+            games,
+            wins: vec![0.0; players],
+            total_scores: vec![0; players],
+        };
+        for game_index in 0..games {
+            // the strategy sitting in `seat`
+            let strategy_of = |seat: usize| (seat + game_index) % players;
+            let mut game = Judgment::with_config(self.config.clone());
+            game.start()?;
+            loop {
+                let transition = match (game.phase(), game.current_player()) {
+                    (Phase::Dealing, _) => Transition::Deal { seed: rng.gen() },
+                    (Phase::Predicting, Some(player)) => {
+                        let view = game.view_for(player).unwrap();
+                        let score = strategies[strategy_of(player)].predict(&view);
+                        Transition::PredictScore { player, score }
+                    }
+                    (Phase::Playing, Some(player)) => {
+                        let view = game.view_for(player).unwrap();
+                        let card = strategies[strategy_of(player)].play(&view);
+                        Transition::Play { player, card }
+                    }
+                    _ => break,
+                };
+                game.update(transition)?;
+            }
+            let result = game.result().expect("the game is over");
+            let winners = result.winners();
+            for seat in &winners {
+                report.wins[strategy_of(*seat)] += 1.0 / winners.len() as f64;
+            }
+            for (seat, score) in game.scores().iter().enumerate() {
+                report.total_scores[strategy_of(seat)] += score;
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use judgment::GameConfig;
+
+    use crate::{HeuristicStrategy, MonteCarloStrategy, RandomStrategy, Strategy};
+
+    use super::Arena;
+
+    #[test]
+    fn test_arena_is_reproducible() {
+        let arena = Arena::new(GameConfig::builder(3, 4).build().unwrap());
+        let run = || {
+            let mut strategies: Vec<Box<dyn Strategy>> = vec![
+                Box::new(RandomStrategy::new(1)),
+                Box::new(HeuristicStrategy),
+                Box::new(MonteCarloStrategy::new(8, 2)),
+            ];
+            arena.run(&mut strategies, 6, 3).unwrap()
+        };
+        let report = run();
+        assert_eq!(report, run());
+        assert_eq!(report.games, 6);
+        let total_wins: f64 = report.wins.iter().sum();
+        assert!((total_wins - 6.0).abs() < 1e-9);
+        let rates: f64 = report.win_rates().iter().sum();
+        assert!((rates - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_heuristic_beats_random() {
+        let arena = Arena::new(GameConfig::builder(4, 7).build().unwrap());
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(HeuristicStrategy),
+            Box::new(RandomStrategy::new(1)),
+            Box::new(HeuristicStrategy),
+            Box::new(RandomStrategy::new(2)),
+        ];
+        let report = arena.run(&mut strategies, 40, 11).unwrap();
+        let scores = report.average_scores();
+        assert!(scores[0] > scores[1] && scores[0] > scores[3]);
+        assert!(scores[2] > scores[1] && scores[2] > scores[3]);
+    }
+
+    #[test]
+    fn test_monte_carlo_beats_random() {
+        let arena = Arena::new(GameConfig::builder(3, 5).build().unwrap());
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(MonteCarloStrategy::new(16, 5)),
+            Box::new(RandomStrategy::new(1)),
+            Box::new(RandomStrategy::new(2)),
+        ];
+        let report = arena.run(&mut strategies, 20, 13).unwrap();
+        let scores = report.average_scores();
+        assert!(scores[0] > scores[1] && scores[0] > scores[2]);
+    }
+}
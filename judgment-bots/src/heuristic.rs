@@ -0,0 +1,265 @@
+use card_deck::standard_deck::{Card, Rank, Suit};
+use judgment::{trick_winner, DuplicateCardRule, PlayerView};
+
+use crate::{played_cards, Strategy};
+
+/// Plays by rules of thumb.
+///
+/// It predicts a trick for every ace and high trump and for some kings, then
+/// tries to win tricks while it is short of its prediction and to lose them
+/// once it has reached it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicStrategy;
+
+impl HeuristicStrategy {
+    /// The number of tricks `hand` can be expected to win.
+    pub(crate) fn estimate(hand: &[Card], trump_suit: Option<&Suit>) -> f64 {
+        hand.iter()
+            .map(|card| {
+                // a joker beats everything
+                let (Some(suit), Some(rank)) = (card.suit(), card.rank()) else {
+                    return 1.0;
+                };
+                let suit_length = hand
+                    .iter()
+                    .filter(|other| other.suit() == Some(suit))
+                    .count();
+                match (Some(suit) == trump_suit, rank) {
+                    (true, Rank::Ace | Rank::King) => 1.0,
+                    (true, Rank::Queen | Rank::Jack) => 0.7,
+                    (true, _) => 0.3,
+                    (false, Rank::Ace) => 0.9,
+                    (false, Rank::King) if suit_length >= 2 => 0.5,
+                    (false, Rank::King) => 0.2,
+                    (false, Rank::Queen) if suit_length >= 3 => 0.2,
+                    (false, _) => 0.0,
+                }
+            })
+            .sum()
+    }
+
+    /// The card to play from `legal_cards` into `trick`, trying to win it if
+    /// `want_win` and to lose it otherwise. Identical cards in the trick are
+    /// settled by `duplicates`.
+    pub(crate) fn choose_card(
+        legal_cards: &[Card],
+        trick: &[Option<Card>],
+        leader: usize,
+        trump_suit: Option<&Suit>,
+        duplicates: DuplicateCardRule,
+        want_win: bool,
+    ) -> Card {
+        let mut cards = legal_cards.to_vec();
+        cards.sort_by_key(|card| strength(card, trump_suit));
+        let lowest = cards[0];
+        let highest = cards[cards.len() - 1];
+        let played = played_cards(trick, leader);
+        if played.is_empty() {
+            // leading
+            return if want_win { highest } else { lowest };
+        }
+        let seat = (leader + played.len()) % trick.len();
+        let (winners, losers): (Vec<Card>, Vec<Card>) = cards.into_iter().partition(|card| {
+            let mut cards = played.clone();
+            cards.push((seat, *card));
+            trick_winner(&cards, trump_suit, duplicates) == Some(seat)
+        });
+        match (want_win, winners.first(), losers.last()) {
+            // win as cheaply as possible
+            (true, Some(cheapest_winner), _) => *cheapest_winner,
+            // cannot win, so throw away the least useful card
+            (true, None, _) => lowest,
+            // lose while getting rid of the most dangerous card
+            (false, _, Some(highest_loser)) => *highest_loser,
+            // forced to win, so at least spend the most dangerous card
+            (false, _, None) => highest,
+        }
+    }
+}
+
+/// Orders cards by how likely they are to win a trick: trumps above all else,
+/// then by rank, with jokers above the highest trump.
+fn strength(card: &Card, trump_suit: Option<&Suit>) -> (bool, u8) {
+    let Some(rank) = card.rank() else {
+        return (true, 15);
+    };
+    let rank = match rank {
+        Rank::Numeric(value) => *value,
+        Rank::Jack => 11,
+        Rank::Queen => 12,
+        Rank::King => 13,
+        Rank::Ace => 14,
+    };
+    (card.suit() == trump_suit, rank)
+}
+
+/// The legal prediction closest to `estimate`.
+pub(crate) fn closest_prediction(legal_predictions: &[u8], estimate: f64) -> u8 {
+    *legal_predictions
+        .iter()
+        .min_by(|a, b| {
+            (f64::from(**a) - estimate)
+                .abs()
+                .total_cmp(&(f64::from(**b) - estimate).abs())
+        })
+        .expect("there is always a legal prediction on the player's turn")
+}
+
+impl Strategy for HeuristicStrategy {
+    fn predict(&mut self, view: &PlayerView) -> u8 {
+        let estimate = HeuristicStrategy::estimate(&view.hand, view.trump_suit.as_ref());
+        closest_prediction(&view.legal_predictions, estimate)
+    }
+
+    fn play(&mut self, view: &PlayerView) -> Card {
+        let prediction = view.predictions[view.player].unwrap_or_default();
+        HeuristicStrategy::choose_card(
+            &view.legal_cards,
+            &view.trick,
+            view.leader.unwrap_or(view.player),
+            view.trump_suit.as_ref(),
+            view.duplicate_cards,
+            view.tricks_won[view.player] < prediction,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use card_deck::standard_deck::{Card, Rank, Suit};
+    use judgment::DuplicateCardRule;
+
+    use super::{closest_prediction, HeuristicStrategy};
+
+    fn card(suit: Suit, rank: Rank) -> Card {
+        Card::new_normal(suit, rank)
+    }
+
+    #[test]
+    fn test_estimate_counts_high_cards_and_trumps() {
+        let strong = [
+            card(Suit::Spades, Rank::Ace),
+            card(Suit::Hearts, Rank::Ace),
+            card(Suit::Clubs, Rank::King),
+            card(Suit::Clubs, Rank::Numeric(4)),
+        ];
+        let weak = [
+            card(Suit::Spades, Rank::Numeric(2)),
+            card(Suit::Hearts, Rank::Numeric(5)),
+            card(Suit::Clubs, Rank::Numeric(7)),
+            card(Suit::Diamonds, Rank::Numeric(9)),
+        ];
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(close(HeuristicStrategy::estimate(&strong, None), 2.3));
+        assert!(close(HeuristicStrategy::estimate(&weak, None), 0.0));
+        assert!(close(
+            HeuristicStrategy::estimate(&weak, Some(&Suit::Diamonds)),
+            0.3
+        ));
+        assert_eq!(closest_prediction(&[0, 1, 2, 3, 4], 2.3), 2);
+        assert_eq!(closest_prediction(&[0, 1, 3, 4], 2.3), 3);
+    }
+
+    #[test]
+    fn test_wins_or_ducks_as_needed() {
+        let legal = [
+            card(Suit::Hearts, Rank::Numeric(3)),
+            card(Suit::Hearts, Rank::Queen),
+            card(Suit::Hearts, Rank::Ace),
+        ];
+        let trick = [Some(card(Suit::Hearts, Rank::Jack)), None];
+        let choose = |want_win| {
+            HeuristicStrategy::choose_card(
+                &legal,
+                &trick,
+                0,
+                Some(&Suit::Spades),
+                DuplicateCardRule::FirstWins,
+                want_win,
+            )
+        };
+        // the cheapest card that beats the jack
+        assert_eq!(choose(true), card(Suit::Hearts, Rank::Queen));
+        // the highest card that stays under the jack
+        assert_eq!(choose(false), card(Suit::Hearts, Rank::Numeric(3)));
+
+        let trumped = [
+            Some(card(Suit::Hearts, Rank::Jack)),
+            Some(card(Suit::Spades, Rank::Numeric(2))),
+            None,
+        ];
+        let choose_trumped = |want_win| {
+            HeuristicStrategy::choose_card(
+                &legal,
+                &trumped,
+                0,
+                Some(&Suit::Spades),
+                DuplicateCardRule::FirstWins,
+                want_win,
+            )
+        };
+        // nothing beats the trump, so keep the good cards
+        assert_eq!(choose_trumped(true), card(Suit::Hearts, Rank::Numeric(3)));
+        assert_eq!(choose_trumped(false), card(Suit::Hearts, Rank::Ace));
+
+        let lead = [None, None];
+        let choose_lead = |want_win| {
+            HeuristicStrategy::choose_card(
+                &legal,
+                &lead,
+                0,
+                Some(&Suit::Spades),
+                DuplicateCardRule::FirstWins,
+                want_win,
+            )
+        };
+        assert_eq!(choose_lead(true), card(Suit::Hearts, Rank::Ace));
+        assert_eq!(choose_lead(false), card(Suit::Hearts, Rank::Numeric(3)));
+    }
+
+    #[test]
+    fn test_duplicate_cards_follow_the_rule() {
+        let legal = [
+            card(Suit::Hearts, Rank::Numeric(3)),
+            card(Suit::Hearts, Rank::Jack),
+            card(Suit::Hearts, Rank::Queen),
+        ];
+        let trick = [
+            Some(card(Suit::Hearts, Rank::Jack)),
+            Some(card(Suit::Hearts, Rank::Numeric(5))),
+            None,
+        ];
+        let choose = |duplicates, want_win| {
+            HeuristicStrategy::choose_card(
+                &legal,
+                &trick,
+                0,
+                Some(&Suit::Spades),
+                duplicates,
+                want_win,
+            )
+        };
+        // the second jack only wins when the last of identical cards does
+        assert_eq!(
+            choose(DuplicateCardRule::FirstWins, true),
+            card(Suit::Hearts, Rank::Queen)
+        );
+        assert_eq!(
+            choose(DuplicateCardRule::LastWins, true),
+            card(Suit::Hearts, Rank::Jack)
+        );
+        assert_eq!(
+            choose(DuplicateCardRule::LastWins, false),
+            card(Suit::Hearts, Rank::Numeric(3))
+        );
+        // and cancelling the jacks hands the trick to the five
+        assert_eq!(
+            choose(DuplicateCardRule::Cancel, true),
+            card(Suit::Hearts, Rank::Queen)
+        );
+        assert_eq!(
+            choose(DuplicateCardRule::Cancel, false),
+            card(Suit::Hearts, Rank::Jack)
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! Computer players for [`judgment`].
+//!
+//! Every bot implements [`Strategy`] and decides from the [`PlayerView`] of
+//! its seat only, so it knows exactly as much as a human in that seat would.
+use card_deck::standard_deck::Card;
+use judgment::PlayerView;
+
+pub use arena::{Arena, ArenaReport};
+pub use heuristic::HeuristicStrategy;
+pub use monte_carlo::MonteCarloStrategy;
+pub use random::RandomStrategy;
+
+mod arena;
+mod heuristic;
+mod monte_carlo;
+mod random;
+mod simulation;
+
+/// A way of playing Judgment.
+pub trait Strategy {
+    /// The number of tricks to predict. Only asked when it is the view's
+    /// player's turn to predict, and must be one of
+    /// [`PlayerView::legal_predictions`].
+    fn predict(&mut self, view: &PlayerView) -> u8;
+
+    /// The card to play. Only asked when it is the view's player's turn to
+    /// play, and must be one of [`PlayerView::legal_cards`].
+    fn play(&mut self, view: &PlayerView) -> Card;
+}
+
+/// The seats and cards of a trick that was led by `leader`, in the order
+/// they were played.
+pub(crate) fn played_cards(trick: &[Option<Card>], leader: usize) -> Vec<(usize, Card)> {
+    let players = trick.len();
+    (0..players)
+        .map(|offset| (leader + offset) % players)
+        .filter_map(|seat| trick[seat].map(|card| (seat, card)))
+        .collect()
+}
@@ -0,0 +1,128 @@
+use card_deck::standard_deck::Card;
+use judgment::PlayerView;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use crate::{
+    heuristic::closest_prediction,
+    simulation::{sample_hands, SimulatedRound},
+    HeuristicStrategy, Strategy,
+};
+
+/// Decides by simulation.
+///
+/// For every decision it deals the cards it cannot see to the other players
+/// at random a number of times, plays each of those deals out with every
+/// player following [`HeuristicStrategy`], and picks the move that hit its
+/// prediction most often.
+#[derive(Debug, Clone)]
+pub struct MonteCarloStrategy {
+    samples: usize,
+    rng: ChaCha20Rng,
+}
+
+impl MonteCarloStrategy {
+    /// A player that simulates `samples` deals per decision.
+    pub fn new(samples: usize, seed: u64) -> Self {
+        MonteCarloStrategy {
+            samples,
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// The round as it stands in `view`, with the unseen cards dealt at
+    /// random.
+    fn sample_round(&mut self, view: &PlayerView) -> SimulatedRound {
+        let hands = sample_hands(view, &mut self.rng);
+        let trump_suit = view.trump_suit;
+        let predictions = view
+            .predictions
+            .iter()
+            .zip(&hands)
+            .map(|(prediction, hand)| {
+                // players yet to predict are assumed to predict like the
+                // heuristic would
+                prediction.unwrap_or_else(|| {
+                    let estimate = HeuristicStrategy::estimate(hand, trump_suit.as_ref());
+                    estimate.round() as u8
+                })
+            })
+            .collect();
+        SimulatedRound {
+            hands,
+            trump_suit,
+            duplicates: view.duplicate_cards,
+            trick: view.trick.clone(),
+            leader: view.leader.unwrap_or(view.player),
+            player: view.player,
+            predictions,
+            tricks_won: view.tricks_won.clone(),
+        }
+    }
+}
+
+impl Strategy for MonteCarloStrategy {
+    fn predict(&mut self, view: &PlayerView) -> u8 {
+        let mut rounds: Vec<SimulatedRound> =
+            (0..self.samples).map(|_| self.sample_round(view)).collect();
+        // bidding happens before any card is played, so the round starts
+        // with the first lead
+        for round in &mut rounds {
+            round.player = round.leader;
+        }
+        let estimate = HeuristicStrategy::estimate(&view.hand, view.trump_suit.as_ref());
+        let hits = |prediction: u8| {
+            rounds
+                .iter()
+                .filter(|round| {
+                    let mut round = (*round).clone();
+                    round.predictions[view.player] = prediction;
+                    round.play_out();
+                    round.tricks_won[view.player] == prediction
+                })
+                .count()
+        };
+        let mut best = closest_prediction(&view.legal_predictions, estimate);
+        let mut best_hits = hits(best);
+        for prediction in view.legal_predictions.iter().copied() {
+            let prediction_hits = hits(prediction);
+            if prediction_hits > best_hits {
+                best = prediction;
+                best_hits = prediction_hits;
+            }
+        }
+        best
+    }
+
+    fn play(&mut self, view: &PlayerView) -> Card {
+        if let [only] = view.legal_cards[..] {
+            return only;
+        }
+        let prediction = view.predictions[view.player].unwrap_or_default();
+        let rounds: Vec<SimulatedRound> =
+            (0..self.samples).map(|_| self.sample_round(view)).collect();
+        let mut best = (view.legal_cards[0], f64::MIN);
+        for card in view.legal_cards.iter().copied() {
+            let value: f64 = rounds
+                .iter()
+                .map(|round| {
+                    let mut round = round.clone();
+                    round.play(card);
+                    round.play_out();
+                    let tricks = round.tricks_won[view.player];
+                    // hitting the prediction is all that matters, but missing
+                    // by less is better than missing by more
+                    if tricks == prediction {
+                        1.0
+                    } else {
+                        -0.01 * f64::from(tricks.abs_diff(prediction))
+                    }
+                })
+                .sum();
+            if value > best.1 {
+                best = (card, value);
+            }
+        }
+        best.0
+    }
+}
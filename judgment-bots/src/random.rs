@@ -0,0 +1,36 @@
+use card_deck::standard_deck::Card;
+use judgment::PlayerView;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::Strategy;
+
+/// Picks uniformly among the legal moves.
+#[derive(Debug, Clone)]
+pub struct RandomStrategy {
+    rng: ChaCha20Rng,
+}
+
+impl RandomStrategy {
+    pub fn new(seed: u64) -> Self {
+        RandomStrategy {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn predict(&mut self, view: &PlayerView) -> u8 {
+        *view
+            .legal_predictions
+            .choose(&mut self.rng)
+            .expect("there is always a legal prediction on the player's turn")
+    }
+
+    fn play(&mut self, view: &PlayerView) -> Card {
+        *view
+            .legal_cards
+            .choose(&mut self.rng)
+            .expect("there is always a legal card on the player's turn")
+    }
+}
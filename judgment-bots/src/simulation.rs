@@ -0,0 +1,156 @@
+use card_deck::standard_deck::{Card, StandardDeckBuilder, Suit};
+use judgment::{trick_winner, DuplicateCardRule, PlayerView};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::HeuristicStrategy;
+
+/// A round whose hands are all known, cheap to clone and play out.
+#[derive(Debug, Clone)]
+pub(crate) struct SimulatedRound {
+    pub(crate) hands: Vec<Vec<Card>>,
+    pub(crate) trump_suit: Option<Suit>,
+    pub(crate) duplicates: DuplicateCardRule,
+    pub(crate) trick: Vec<Option<Card>>,
+    pub(crate) leader: usize,
+    pub(crate) player: usize,
+    pub(crate) predictions: Vec<u8>,
+    pub(crate) tricks_won: Vec<u8>,
+}
+
+impl SimulatedRound {
+    /// The cards the current player may play, following the engine's rule
+    /// that the led suit must be followed when possible. Jokers may always be
+    /// played, and a led joker leaves the suit to the next card.
+    pub(crate) fn legal_cards(&self) -> Vec<Card> {
+        let hand = &self.hands[self.player];
+        let players = self.trick.len();
+        let led_suit = (0..players)
+            .filter_map(|offset| self.trick[(self.leader + offset) % players])
+            .find_map(|card| card.suit().copied());
+        match led_suit {
+            Some(suit) if hand.iter().any(|card| card.suit() == Some(&suit)) => hand
+                .iter()
+                .filter(|card| card.is_joker() || card.suit() == Some(&suit))
+                .copied()
+                .collect(),
+            _ => hand.clone(),
+        }
+    }
+
+    /// Play `card` for the current player, who must hold it.
+    pub(crate) fn play(&mut self, card: Card) {
+        let hand = &mut self.hands[self.player];
+        let position = hand
+            .iter()
+            .position(|held| *held == card)
+            .expect("the player must hold the played card");
+        hand.swap_remove(position);
+        self.trick[self.player] = Some(card);
+        let players = self.trick.len();
+        self.player = (self.player + 1) % players;
+        if self.trick.iter().all(|card| card.is_some()) {
+            let cards: Vec<(usize, Card)> = (0..players)
+                .map(|offset| (self.leader + offset) % players)
+                .map(|seat| (seat, self.trick[seat].unwrap()))
+                .collect();
+            let winner = trick_winner(&cards, self.trump_suit.as_ref(), self.duplicates)
+                .expect("a complete trick has a winner");
+            self.tricks_won[winner] += 1;
+            self.leader = winner;
+            self.player = winner;
+            self.trick.iter_mut().for_each(|card| *card = None);
+        }
+    }
+
+    pub(crate) fn is_over(&self) -> bool {
+        self.hands.iter().all(|hand| hand.is_empty())
+    }
+
+    /// Play the rest of the round with every player following
+    /// [`HeuristicStrategy`].
+    pub(crate) fn play_out(&mut self) {
+        while !self.is_over() {
+            let want_win = self.tricks_won[self.player] < self.predictions[self.player];
+            let card = HeuristicStrategy::choose_card(
+                &self.legal_cards(),
+                &self.trick,
+                self.leader,
+                self.trump_suit.as_ref(),
+                self.duplicates,
+                want_win,
+            );
+            self.play(card);
+        }
+    }
+}
+
+/// Deal the cards `view`'s player cannot see to the other players at random,
+/// returning every player's hand.
+///
+/// Players who have failed to follow a suit this round are known to hold none
+/// of it, and are dealt accordingly whenever that is possible.
+pub(crate) fn sample_hands<R: Rng>(view: &PlayerView, rng: &mut R) -> Vec<Vec<Card>> {
+    let players = view.hand_sizes.len();
+    let mut unseen: Vec<Card> = StandardDeckBuilder::new()
+        .jokers(view.jokers.into())
+        .subdecks(view.decks.into())
+        .build()
+        .draw_n((52 + usize::from(view.jokers)) * usize::from(view.decks))
+        .collect();
+    let seen = view
+        .completed_tricks
+        .iter()
+        .flat_map(|trick| trick.cards.iter().map(|(_, card)| card))
+        .chain(view.trick.iter().flatten())
+        .chain(&view.turned_up);
+    for card in view.hand.iter().chain(seen) {
+        if let Some(position) = unseen.iter().position(|other| other == card) {
+            unseen.swap_remove(position);
+        }
+    }
+
+    let mut voids = vec![Vec::new(); players];
+    let current_trick: Vec<(usize, Card)> = (0..players)
+        .map(|offset| (view.leader.unwrap_or(view.player) + offset) % players)
+        .filter_map(|seat| view.trick[seat].map(|card| (seat, card)))
+        .collect();
+    for cards in view
+        .completed_tricks
+        .iter()
+        .map(|trick| &trick.cards)
+        .chain([&current_trick])
+    {
+        let Some(led_suit) = cards.iter().find_map(|(_, card)| card.suit()) else {
+            continue;
+        };
+        for (seat, card) in cards {
+            if card.suit().is_some_and(|suit| suit != led_suit) {
+                voids[*seat].push(*led_suit);
+            }
+        }
+    }
+
+    let deal = |rng: &mut R, respect_voids: bool| {
+        let mut hands: Vec<Vec<Card>> = vec![Vec::new(); players];
+        hands[view.player] = view.hand.clone();
+        let mut cards = unseen.clone();
+        cards.shuffle(rng);
+        for card in cards {
+            let open_seats: Vec<usize> = (0..players)
+                .filter(|seat| *seat != view.player && hands[*seat].len() < view.hand_sizes[*seat])
+                .filter(|seat| {
+                    !respect_voids || card.suit().is_none_or(|suit| !voids[*seat].contains(suit))
+                })
+                .collect();
+            if let Some(seat) = open_seats.choose(rng) {
+                hands[*seat].push(card);
+            }
+        }
+        let complete = (0..players).all(|seat| hands[seat].len() == view.hand_sizes[seat]);
+        complete.then_some(hands)
+    };
+    (0..20)
+        .find_map(|_| deal(rng, true))
+        .or_else(|| deal(rng, false))
+        .expect("there are always enough unseen cards to fill every hand")
+}
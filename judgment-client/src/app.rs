@@ -1,5 +1,6 @@
 use either::Either;
 use gloo_net::http::Request;
+use judgment::GameConfigError;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
@@ -7,7 +8,12 @@ use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
 use yew::{html, Component, Html, InputEvent};
 
-use crate::{player::Player, scores::Scores, trick::Trick, InvalidRoomId};
+use crate::{
+    player::Player,
+    scores::Scores,
+    trick::{CompletedTricks, Trick},
+    InvalidRoomId,
+};
 
 #[derive(Debug, PartialEq, Default)]
 pub struct App {
@@ -45,6 +51,7 @@ impl Component for App {
                 html! {
                     <div class="app">
                         <Trick room_id={room_id}/>
+                        <CompletedTricks room_id={room_id}/>
                         <Player room_id={room_id} token={token.clone()}/>
                         <Scores room_id={room_id}/>
                         <details>
@@ -160,7 +167,7 @@ impl Component for App {
                         ctx.link().send_future(async move {
                             match create_room(players, starting_hand_size, decks).await {
                                 Ok(room) => Msg::CreatedRoom(room),
-                                Err(CreateRoomError::ServerFull(err)) => {
+                                Err(CreateRoomError::RoomCreation(err)) => {
                                     Msg::DisplayError(err.to_string())
                                 }
                                 Err(
@@ -209,7 +216,7 @@ async fn create_room(
         .await?;
     let body = response.text().await?;
     let mut json_deserializer = serde_json::Deserializer::from_str(&body);
-    let deserialized: Either<RoomPayload, ServerFull> =
+    let deserialized: Either<RoomPayload, RoomCreationError> =
         either::serde_untagged::deserialize(&mut json_deserializer)?;
     match deserialized {
         Either::Left(room) => Ok(room),
@@ -226,10 +233,18 @@ pub struct RoomPayload {
 #[error("server's room capacity is full")]
 struct ServerFull;
 
+#[derive(Debug, thiserror::Error, Deserialize)]
+enum RoomCreationError {
+    #[error(transparent)]
+    ServerFull(#[from] ServerFull),
+    #[error(transparent)]
+    InvalidConfig(#[from] GameConfigError),
+}
+
 #[derive(Debug, thiserror::Error)]
 enum CreateRoomError {
     #[error(transparent)]
-    ServerFull(#[from] ServerFull),
+    RoomCreation(#[from] RoomCreationError),
     #[error(transparent)]
     NetworkError(#[from] gloo_net::Error),
     #[error(transparent)]
@@ -3,7 +3,7 @@ use std::{collections::HashMap, time::Duration};
 use card_deck::standard_deck::{Card, Suit};
 use either::Either;
 use gloo_net::http::Request;
-use judgment::InvalidTransition;
+use judgment::{Claim, InvalidRewind, InvalidTransition, Phase};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use wasm_bindgen::JsCast;
@@ -16,6 +16,7 @@ use crate::{InvalidPlayerId, InvalidRoomId};
 pub(crate) struct Player {
     hand: HashMap<Suit, Vec<Card>>,
     prediction_input: Option<u8>,
+    turn_state: Option<TurnState>,
 }
 
 impl Default for Player {
@@ -27,6 +28,7 @@ impl Default for Player {
         Player {
             hand,
             prediction_input: None,
+            turn_state: None,
         }
     }
 }
@@ -41,9 +43,14 @@ pub(crate) enum Msg {
     Play(Card),
     QueryHand,
     HandUpdated(HashMap<Suit, Vec<Card>>),
+    QueryTurnState,
+    TurnStateUpdated(TurnState),
     PredictionInput(u8),
     Predict,
+    Claim,
+    AnswerClaim(bool),
     Deal,
+    Undo,
     DisplayError(String),
 }
 
@@ -53,6 +60,7 @@ impl Component for Player {
 
     fn create(ctx: &yew::Context<Self>) -> Self {
         ctx.link().send_message(Msg::QueryHand);
+        ctx.link().send_message(Msg::QueryTurnState);
         Player::default()
     }
 
@@ -63,7 +71,24 @@ impl Component for Player {
             Msg::PredictionInput(input_element.value().parse().unwrap())
         });
         let predict_callback = ctx.link().callback(|_| Msg::Predict);
+        let claim_callback = ctx.link().callback(|_| Msg::Claim);
+        let accept_callback = ctx.link().callback(|_| Msg::AnswerClaim(true));
+        let reject_callback = ctx.link().callback(|_| Msg::AnswerClaim(false));
         let deal_callback = ctx.link().callback(|_| Msg::Deal);
+        let undo_callback = ctx.link().callback(|_| Msg::Undo);
+        let can_predict = self
+            .turn_state
+            .as_ref()
+            .is_some_and(|state| !state.legal_predictions.is_empty());
+        // claims are made instead of playing a card
+        let can_claim = self
+            .turn_state
+            .as_ref()
+            .is_some_and(|state| !state.legal_cards.is_empty());
+        let claim = self
+            .turn_state
+            .as_ref()
+            .and_then(|state| state.claim.as_ref());
         html! {
             <>
                 <div class="hand">
@@ -73,7 +98,8 @@ impl Component for Player {
                             {
                                 cards.iter().map(|card| {
                                     let card = *card;
-                                    html!{<button class="playable" onclick={ctx.link().callback(move |_| Msg::Play(card))}>{card.to_string()}</button>}}).collect::<Html>()
+                                    let legal = self.turn_state.as_ref().is_some_and(|state| state.legal_cards.contains(&card));
+                                    html!{<button class="playable" disabled={!legal} onclick={ctx.link().callback(move |_| Msg::Play(card))}>{card.to_string()}</button>}}).collect::<Html>()
                             }
                         </div>
                     }).collect::<Html>()
@@ -82,9 +108,26 @@ impl Component for Player {
                 <div class="prediction">
                     <label for="players">{"Score Prediction: "}</label>
                     <input type="number" id="prediction" min=0 max=13 placeholder="Score Prediction" oninput={prediction_input_callback}/>
-                    <button type="button" onclick={predict_callback}>{"Predict"}</button>
+                    <button type="button" disabled={!can_predict} onclick={predict_callback}>{"Predict"}</button>
+                </div>
+                <div class="claim">
+                    <button type="button" title="Claim every trick left in the round instead of leading" disabled={!can_claim} onclick={claim_callback}>{"Claim the Rest"}</button>
+                    {
+                        if let Some(claim) = claim {
+                            html! {
+                                <>
+                                    <p>{format!("Player {} claims every remaining trick", claim.player)}</p>
+                                    <button type="button" onclick={accept_callback}>{"Accept"}</button>
+                                    <button type="button" onclick={reject_callback}>{"Reject"}</button>
+                                </>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
                 </div>
                 <button type="button" onclick={deal_callback}>{"Deal"}</button>
+                <button type="button" title="Takes back the last move once every player asks for it" onclick={undo_callback}>{"Undo"}</button>
             </>
         }
     }
@@ -135,6 +178,36 @@ impl Component for Player {
                     true
                 }
             }
+            Msg::QueryTurnState => {
+                let token = ctx.props().token.clone();
+                ctx.link().send_future(async move {
+                    match query_turn_state(&token).await {
+                        Ok(state) => Msg::TurnStateUpdated(state),
+                        Err(QueryHandError::ResourceDoesNotExist(err)) => {
+                            Msg::DisplayError(err.to_string())
+                        }
+                        Err(QueryHandError::NetworkError(_) | QueryHandError::SerdeError(_)) => {
+                            Msg::DisplayError(
+                                "server or network related issue, try again after some time"
+                                    .to_string(),
+                            )
+                        }
+                    }
+                });
+                false
+            }
+            Msg::TurnStateUpdated(state) => {
+                ctx.link().send_future(async move {
+                    sleep(Duration::from_secs(2)).await;
+                    Msg::QueryTurnState
+                });
+                if self.turn_state.as_ref() == Some(&state) {
+                    false
+                } else {
+                    self.turn_state = Some(state);
+                    true
+                }
+            }
             Msg::DisplayError(err) => {
                 gloo_dialogs::alert(&err);
                 false
@@ -162,6 +235,39 @@ impl Component for Player {
                 }
                 false
             }
+            Msg::Claim => {
+                let token = ctx.props().token.clone();
+                ctx.link().send_future(async move {
+                    match play(&token, &Action::Claim).await {
+                        Ok(_) => Msg::QueryTurnState,
+                        Err(PlayError::Action(err)) => Msg::DisplayError(err.to_string()),
+                        Err(PlayError::Network(_) | PlayError::Serde(_)) => Msg::DisplayError(
+                            "server or network related issue, try again after some time"
+                                .to_string(),
+                        ),
+                    }
+                });
+                false
+            }
+            Msg::AnswerClaim(accept) => {
+                let token = ctx.props().token.clone();
+                let action = if accept {
+                    Action::AcceptClaim
+                } else {
+                    Action::RejectClaim
+                };
+                ctx.link().send_future(async move {
+                    match play(&token, &action).await {
+                        Ok(_) => Msg::QueryHand,
+                        Err(PlayError::Action(err)) => Msg::DisplayError(err.to_string()),
+                        Err(PlayError::Network(_) | PlayError::Serde(_)) => Msg::DisplayError(
+                            "server or network related issue, try again after some time"
+                                .to_string(),
+                        ),
+                    }
+                });
+                false
+            }
             Msg::Deal => {
                 let token = ctx.props().token.clone();
                 ctx.link().send_future(async move {
@@ -176,6 +282,20 @@ impl Component for Player {
                 });
                 false
             }
+            Msg::Undo => {
+                let token = ctx.props().token.clone();
+                ctx.link().send_future(async move {
+                    match play(&token, &Action::Undo).await {
+                        Ok(_) => Msg::QueryHand,
+                        Err(PlayError::Action(err)) => Msg::DisplayError(err.to_string()),
+                        Err(PlayError::Network(_) | PlayError::Serde(_)) => Msg::DisplayError(
+                            "server or network related issue, try again after some time"
+                                .to_string(),
+                        ),
+                    }
+                });
+                false
+            }
         }
     }
 }
@@ -198,7 +318,7 @@ async fn query_hand(token: &str) -> Result<HashMap<Suit, Vec<Card>>, QueryHandEr
                         suit,
                         cards
                             .iter()
-                            .filter(|card| card.suit().unwrap() == &suit)
+                            .filter(|card| card.suit() == Some(&suit))
                             .cloned()
                             .collect::<Vec<Card>>(),
                     )
@@ -210,6 +330,31 @@ async fn query_hand(token: &str) -> Result<HashMap<Suit, Vec<Card>>, QueryHandEr
     }
 }
 
+/// Whose turn it is and what the player is allowed to do.
+#[derive(Debug, PartialEq, Deserialize)]
+pub(crate) struct TurnState {
+    phase: Phase,
+    current_player: Option<usize>,
+    legal_cards: Vec<Card>,
+    legal_predictions: Vec<u8>,
+    claim: Option<Claim>,
+}
+
+async fn query_turn_state(token: &str) -> Result<TurnState, QueryHandError> {
+    let response = Request::get("/judgment/api/state")
+        .header("Authorization", &format!("Bearer {token}"))
+        .send()
+        .await?;
+    let body = response.text().await?;
+    let mut json_deserializer = serde_json::Deserializer::from_str(&body);
+    let deserialized: Either<TurnState, ResourceDoesNotExist> =
+        either::serde_untagged::deserialize(&mut json_deserializer)?;
+    match deserialized {
+        Either::Left(state) => Ok(state),
+        Either::Right(err) => Err(err.into()),
+    }
+}
+
 #[derive(Debug, thiserror::Error, Deserialize)]
 enum ResourceDoesNotExist {
     #[error(transparent)]
@@ -245,15 +390,23 @@ async fn play(token: &str, action: &Action) -> Result<(), PlayError> {
 enum Action {
     Play(Card),
     PredictScore(u8),
+    Claim,
+    AcceptClaim,
+    RejectClaim,
     Deal,
+    Undo,
 }
 
+// variant names mirror the server's `PlayError`
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, thiserror::Error, Deserialize)]
 enum ActionError {
     #[error(transparent)]
     InvalidRoomId(#[from] InvalidRoomId),
     #[error(transparent)]
     InvalidTransition(#[from] InvalidTransition),
+    #[error(transparent)]
+    InvalidRewind(#[from] InvalidRewind),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -2,6 +2,8 @@ use std::{iter, time::Duration};
 
 use either::Either;
 use gloo_net::http::Request;
+use judgment::{GameResult, Teams};
+use serde::Deserialize;
 use uuid::Uuid;
 use yew::{html, platform::time::sleep, Component, Html, Properties};
 
@@ -12,6 +14,15 @@ pub(crate) struct Scores {
     predictions: Vec<Option<u8>>,
     round_scores: Vec<u8>,
     scores: Vec<i64>,
+    team_scores: Option<TeamScores>,
+    result: Option<GameResult>,
+}
+
+/// The score of every team, along with who plays in which team.
+#[derive(Debug, PartialEq, Deserialize)]
+pub(crate) struct TeamScores {
+    teams: Teams,
+    scores: Vec<i64>,
 }
 
 pub(crate) enum Msg {
@@ -21,6 +32,10 @@ pub(crate) enum Msg {
     ScoresUpdated(Vec<i64>),
     QueryRoundScores,
     RoundScoresUpdated(Vec<u8>),
+    QueryTeamScores,
+    TeamScoresUpdated(Option<TeamScores>),
+    QueryResult,
+    ResultUpdated(Option<GameResult>),
     DisplayError(String),
 }
 
@@ -37,23 +52,27 @@ impl Component for Scores {
         ctx.link().send_message(Msg::QueryPredictions);
         ctx.link().send_message(Msg::QueryScores);
         ctx.link().send_message(Msg::QueryRoundScores);
+        ctx.link().send_message(Msg::QueryTeamScores);
+        ctx.link().send_message(Msg::QueryResult);
         Scores::default()
     }
 
     fn view(&self, _ctx: &yew::Context<Self>) -> yew::Html {
         html! {
             <div>
+                {self.result.as_ref().map(final_results).unwrap_or_default()}
                 <details class="scores" open=true>
                     <summary>{"Predictions"}</summary>
                     <table>
                         <thead>
+                            {self.team_header(self.predictions.len())}
                             <tr>
-                                {iter::once(html!{<th scope="row">{"Player"}</th>}).chain((0..self.predictions.len()).map(|idx| html!{<th scope="col">{idx}</th>})).collect::<Html>()}
+                                {iter::once(html!{<th scope="row">{"Player"}</th>}).chain(self.seating(self.predictions.len()).into_iter().map(|idx| html!{<th scope="col">{idx}</th>})).collect::<Html>()}
                             </tr>
                         </thead>
                         <tbody>
                             <tr>
-                                {iter::once(html!{<th scope="row">{"Prediction"}</th>}).chain(self.predictions.iter().map(|pred| html!{<td>{pred.map(|s| s.to_string()).unwrap_or("-".to_string())}</td>})).collect::<Html>()}
+                                {iter::once(html!{<th scope="row">{"Prediction"}</th>}).chain(self.seating(self.predictions.len()).into_iter().map(|idx| html!{<td>{self.predictions[idx].map(|s| s.to_string()).unwrap_or("-".to_string())}</td>})).collect::<Html>()}
                             </tr>
                         </tbody>
                     </table>
@@ -62,14 +81,16 @@ impl Component for Scores {
                     <summary>{"Round Scores"}</summary>
                     <table>
                         <thead>
+                            {self.team_header(self.round_scores.len())}
                             <tr>
-                                {iter::once(html!{<th scope="row">{"Player"}</th>}).chain((0..self.round_scores.len()).map(|idx| html!{<th scope="col">{idx}</th>})).collect::<Html>()}
+                                {iter::once(html!{<th scope="row">{"Player"}</th>}).chain(self.seating(self.round_scores.len()).into_iter().map(|idx| html!{<th scope="col">{idx}</th>})).collect::<Html>()}
                             </tr>
                         </thead>
                         <tbody>
                             <tr>
-                                {iter::once(html!{<th scope="row">{"Score"}</th>}).chain(self.round_scores.iter().map(|score| html!{<td>{score}</td>})).collect::<Html>()}
+                                {iter::once(html!{<th scope="row">{"Score"}</th>}).chain(self.seating(self.round_scores.len()).into_iter().map(|idx| html!{<td>{self.round_scores[idx]}</td>})).collect::<Html>()}
                             </tr>
+                            {self.team_row("Team Score", self.round_scores.len(), |team_scores| team_scores.teams.tricks(&self.round_scores).into_iter().map(i64::from).collect())}
                         </tbody>
                     </table>
                 </details>
@@ -77,14 +98,16 @@ impl Component for Scores {
                     <summary>{"Game Scores"}</summary>
                     <table>
                         <thead>
+                            {self.team_header(self.scores.len())}
                             <tr>
-                                {iter::once(html!{<th scope="row">{"Player"}</th>}).chain((0..self.scores.len()).map(|idx| html!{<th scope="col">{idx}</th>})).collect::<Html>()}
+                                {iter::once(html!{<th scope="row">{"Player"}</th>}).chain(self.seating(self.scores.len()).into_iter().map(|idx| html!{<th scope="col">{idx}</th>})).collect::<Html>()}
                             </tr>
                         </thead>
                         <tbody>
                             <tr>
-                                {iter::once(html!{<th scope="row">{"Score"}</th>}).chain(self.scores.iter().map(|score| html!{<td>{score}</td>})).collect::<Html>()}
+                                {iter::once(html!{<th scope="row">{"Score"}</th>}).chain(self.seating(self.scores.len()).into_iter().map(|idx| html!{<td>{self.scores[idx]}</td>})).collect::<Html>()}
                             </tr>
+                            {self.team_row("Team Score", self.scores.len(), |team_scores| team_scores.scores.clone())}
                         </tbody>
                     </table>
                 </details>
@@ -188,6 +211,68 @@ impl Component for Scores {
                     true
                 }
             }
+            Msg::QueryTeamScores => {
+                let room_id = ctx.props().room_id;
+                ctx.link().send_future(async move {
+                    match query_team_scores(room_id).await {
+                        Ok(team_scores) => Msg::TeamScoresUpdated(team_scores),
+                        Err(QueryScoresError::InvalidRoomId(err)) => {
+                            Msg::DisplayError(err.to_string())
+                        }
+                        Err(QueryScoresError::Network(_) | QueryScoresError::Serde(_)) => {
+                            Msg::DisplayError(
+                                "server or network related issue, try again after some time"
+                                    .to_string(),
+                            )
+                        }
+                    }
+                });
+                false
+            }
+            Msg::TeamScoresUpdated(team_scores) => {
+                if self.team_scores == team_scores {
+                    ctx.link().send_future(async move {
+                        sleep(Duration::from_secs(5)).await;
+                        Msg::QueryTeamScores
+                    });
+                    false
+                } else {
+                    self.team_scores = team_scores;
+                    ctx.link().send_message(Msg::QueryTeamScores);
+                    true
+                }
+            }
+            Msg::QueryResult => {
+                let room_id = ctx.props().room_id;
+                ctx.link().send_future(async move {
+                    match query_result(room_id).await {
+                        Ok(result) => Msg::ResultUpdated(result),
+                        Err(QueryScoresError::InvalidRoomId(err)) => {
+                            Msg::DisplayError(err.to_string())
+                        }
+                        Err(QueryScoresError::Network(_) | QueryScoresError::Serde(_)) => {
+                            Msg::DisplayError(
+                                "server or network related issue, try again after some time"
+                                    .to_string(),
+                            )
+                        }
+                    }
+                });
+                false
+            }
+            Msg::ResultUpdated(result) => {
+                if self.result == result {
+                    ctx.link().send_future(async move {
+                        sleep(Duration::from_secs(5)).await;
+                        Msg::QueryResult
+                    });
+                    false
+                } else {
+                    self.result = result;
+                    ctx.link().send_message(Msg::QueryResult);
+                    true
+                }
+            }
             Msg::DisplayError(err) => {
                 gloo_dialogs::alert(&err);
                 false
@@ -196,6 +281,125 @@ impl Component for Scores {
     }
 }
 
+impl Scores {
+    /// The teams, if the players play in teams and there is a column for
+    /// each of the `players`.
+    fn teams(&self, players: usize) -> Option<&TeamScores> {
+        self.team_scores
+            .as_ref()
+            .filter(|team_scores| team_scores.teams.seats().len() == players)
+    }
+
+    /// The order of the columns for `players`, with teammates next to each
+    /// other.
+    fn seating(&self, players: usize) -> Vec<usize> {
+        match self.teams(players) {
+            Some(team_scores) => (0..team_scores.teams.count())
+                .flat_map(|team| team_scores.teams.members(team))
+                .collect(),
+            None => (0..players).collect(),
+        }
+    }
+
+    /// A row naming the team above every group of teammates, if the players
+    /// play in teams.
+    fn team_header(&self, players: usize) -> Html {
+        self.team_row("Team", players, |team_scores| {
+            (0..team_scores.teams.count() as i64).collect()
+        })
+    }
+
+    /// A row with one cell per team spanning its players, if the players
+    /// play in teams.
+    fn team_row(
+        &self,
+        title: &str,
+        players: usize,
+        values: impl Fn(&TeamScores) -> Vec<i64>,
+    ) -> Html {
+        let Some(team_scores) = self.teams(players) else {
+            return Html::default();
+        };
+        html! {
+            <tr>
+                {iter::once(html!{<th scope="row">{title}</th>}).chain(values(team_scores).into_iter().enumerate().map(|(team, value)| html!{<th scope="colgroup" colspan={team_scores.teams.members(team).count().to_string()}>{value}</th>})).collect::<Html>()}
+            </tr>
+        }
+    }
+}
+
+fn final_results(result: &GameResult) -> Html {
+    html! {
+        <details class="scores" open=true>
+            <summary>{"Final Results"}</summary>
+            <table>
+                <thead>
+                    <tr>
+                        <th scope="col">{"Rank"}</th>
+                        <th scope="col">{"Player"}</th>
+                        <th scope="col">{"Score"}</th>
+                        <th scope="col">{"Hit Rate"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {result.ranking.iter().map(|standing| html!{
+                        <tr>
+                            <td>{standing.rank}</td>
+                            <td>{standing.player}</td>
+                            <td>{standing.score}</td>
+                            <td>{format!("{:.0}%", result.stats[standing.player].hit_rate() * 100.0)}</td>
+                        </tr>
+                    }).collect::<Html>()}
+                </tbody>
+            </table>
+            <table>
+                <thead>
+                    <tr>
+                        {iter::once(html!{<th scope="row">{"Round"}</th>}).chain((0..result.stats.len()).map(|idx| html!{<th scope="col">{idx}</th>})).collect::<Html>()}
+                    </tr>
+                </thead>
+                <tbody>
+                    {result.rounds.iter().enumerate().map(|(idx, round)| html!{
+                        <tr>
+                            {iter::once(html!{<th scope="row">{format!("{} ({} cards)", idx + 1, round.hand_size)}</th>}).chain((0..round.deltas.len()).map(|player| html!{<td>{format!("{}/{} ({:+})", round.tricks[player], round.predictions[player], round.deltas[player])}</td>})).collect::<Html>()}
+                        </tr>
+                    }).collect::<Html>()}
+                </tbody>
+            </table>
+        </details>
+    }
+}
+
+async fn query_result(room_id: Uuid) -> Result<Option<GameResult>, QueryScoresError> {
+    let response = Request::get("/judgment/api/result")
+        .query([("room_id", room_id.to_string())])
+        .send()
+        .await?;
+    let body = response.text().await?;
+    let mut json_deserializer = serde_json::Deserializer::from_str(&body);
+    let deserialized: Either<Option<GameResult>, InvalidRoomId> =
+        either::serde_untagged::deserialize(&mut json_deserializer)?;
+    match deserialized {
+        Either::Left(result) => Ok(result),
+        Either::Right(err) => Err(err.into()),
+    }
+}
+
+async fn query_team_scores(room_id: Uuid) -> Result<Option<TeamScores>, QueryScoresError> {
+    let response = Request::get("/judgment/api/team_scores")
+        .query([("room_id", room_id.to_string())])
+        .send()
+        .await?;
+    let body = response.text().await?;
+    let mut json_deserializer = serde_json::Deserializer::from_str(&body);
+    let deserialized: Either<Option<TeamScores>, InvalidRoomId> =
+        either::serde_untagged::deserialize(&mut json_deserializer)?;
+    match deserialized {
+        Either::Left(team_scores) => Ok(team_scores),
+        Either::Right(err) => Err(err.into()),
+    }
+}
+
 async fn query_scores(room_id: Uuid) -> Result<Vec<i64>, QueryScoresError> {
     let response = Request::get("/judgment/api/scores")
         .query([("room_id", room_id.to_string())])
@@ -52,7 +52,7 @@ impl Component for Trick {
                         self.cards.iter().map(|opt_card| match opt_card {
                             None => html!{<div class="trick_card">{"\u{1f0a0}"}</div>},
                             Some(card) => {
-                                let class = format!("trick_card {}", card.suit().unwrap().name());
+                                let class = format!("trick_card {}", card.suit().map_or("joker", |suit| suit.name()));
                                 html!{<div class={class}>{card.to_string()}</div>}
                             }
                         }).collect::<Html>()
@@ -129,6 +129,108 @@ async fn query_trick(room_id: Uuid) -> Result<judgment::Trick, QueryError> {
     }
 }
 
+/// The tricks already played in the current round, most recent first.
+#[derive(Debug, PartialEq, Default)]
+pub(crate) struct CompletedTricks {
+    tricks: Vec<judgment::CompletedTrick>,
+}
+
+pub(crate) enum CompletedTricksMsg {
+    QueryTricks,
+    TricksUpdated(Vec<judgment::CompletedTrick>),
+    DisplayError(String),
+}
+
+impl Component for CompletedTricks {
+    type Message = CompletedTricksMsg;
+    type Properties = Props;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        ctx.link().send_message(CompletedTricksMsg::QueryTricks);
+        CompletedTricks::default()
+    }
+
+    fn view(&self, _ctx: &yew::Context<Self>) -> yew::Html {
+        html! {
+            <details>
+                <summary>{format!("Previous Tricks ({})", self.tricks.len())}</summary>
+                <table>
+                    <thead>
+                        <tr>
+                            <th scope="col">{"Trick"}</th>
+                            <th scope="col">{"Leader"}</th>
+                            <th scope="col">{"Cards"}</th>
+                            <th scope="col">{"Winner"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            self.tricks.iter().enumerate().rev().map(|(idx, trick)| html!{
+                                <tr>
+                                    <td>{idx + 1}</td>
+                                    <td>{trick.leader}</td>
+                                    <td>{
+                                        trick.cards.iter().map(|(player, card)| {
+                                            let class = format!("trick_card {}", card.suit().map_or("joker", |suit| suit.name()));
+                                            html!{<span class={class} title={format!("Player {player}")}>{card.to_string()}</span>}
+                                        }).collect::<Html>()
+                                    }</td>
+                                    <td>{trick.winner}</td>
+                                </tr>
+                            }).collect::<Html>()
+                        }
+                    </tbody>
+                </table>
+            </details>
+        }
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            CompletedTricksMsg::QueryTricks => {
+                let room_id = ctx.props().room_id;
+                ctx.link().send_future(async move {
+                    match query_completed_tricks(room_id).await {
+                        Ok(tricks) => CompletedTricksMsg::TricksUpdated(tricks),
+                        Err(err) => CompletedTricksMsg::DisplayError(err.to_string()),
+                    }
+                });
+                false
+            }
+            CompletedTricksMsg::TricksUpdated(tricks) => {
+                ctx.link().send_message(CompletedTricksMsg::QueryTricks);
+                if self.tricks == tricks {
+                    false
+                } else {
+                    self.tricks = tricks;
+                    true
+                }
+            }
+            CompletedTricksMsg::DisplayError(err) => {
+                gloo_dialogs::alert(&err);
+                false
+            }
+        }
+    }
+}
+
+async fn query_completed_tricks(
+    room_id: Uuid,
+) -> Result<Vec<judgment::CompletedTrick>, QueryError> {
+    let response = Request::get("/judgment/api/completed_tricks")
+        .query([("room_id", room_id.to_string())])
+        .send()
+        .await?;
+    let body = response.text().await?;
+    let mut json_deserializer = serde_json::Deserializer::from_str(&body);
+    let deserialized: Either<Vec<judgment::CompletedTrick>, InvalidRoomId> =
+        either::serde_untagged::deserialize(&mut json_deserializer)?;
+    match deserialized {
+        Either::Left(tricks) => Ok(tricks),
+        Either::Right(err) => Err(err.into()),
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 enum QueryError {
     #[error(transparent)]
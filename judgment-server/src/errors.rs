@@ -1,5 +1,5 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
-use judgment::InvalidTransition;
+use judgment::{GameConfigError, InvalidRewind, InvalidTransition};
 use serde::Serialize;
 
 #[derive(Debug, thiserror::Error, Serialize)]
@@ -36,6 +36,24 @@ impl IntoResponse for ServerFull {
     }
 }
 
+#[derive(Debug, thiserror::Error, Serialize)]
+pub enum CreateRoomError {
+    #[error(transparent)]
+    ServerFull(#[from] ServerFull),
+    #[error(transparent)]
+    InvalidConfig(#[from] GameConfigError),
+}
+
+impl IntoResponse for CreateRoomError {
+    fn into_response(self) -> axum::response::Response {
+        let status = match self {
+            CreateRoomError::ServerFull(_) => StatusCode::CONFLICT,
+            CreateRoomError::InvalidConfig(_) => StatusCode::BAD_REQUEST,
+        };
+        (status, Json(self)).into_response()
+    }
+}
+
 #[derive(Debug, thiserror::Error, Serialize)]
 #[error("no such room exists")]
 pub struct InvalidRoomId;
@@ -66,6 +84,30 @@ pub enum PlayError {
     InvalidRoomId(#[from] InvalidRoomId),
     #[error(transparent)]
     InvalidTransition(#[from] InvalidTransition),
+    #[error(transparent)]
+    InvalidRewind(#[from] InvalidRewind),
+    #[error(transparent)]
+    FairDeal(#[from] FairDealError),
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+pub enum FairDealError {
+    #[error("this room does not deal with commit-reveal")]
+    NotEnabled,
+    #[error("no deal is waiting for commitments")]
+    NoPendingDeal,
+    #[error("the cards are dealt once every player has revealed their entropy")]
+    DealIsAutomatic,
+    #[error("not a player of this room")]
+    InvalidPlayer,
+    #[error("already committed to entropy for this deal")]
+    AlreadyCommitted,
+    #[error("every player must commit before anyone reveals")]
+    CommitmentsMissing,
+    #[error("already revealed entropy for this deal")]
+    AlreadyRevealed,
+    #[error("the entropy does not match the commitment")]
+    RevealMismatch,
 }
 
 impl IntoResponse for PlayError {
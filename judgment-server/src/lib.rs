@@ -11,10 +11,13 @@ use axum::{
     Json, Router,
 };
 use card_deck::standard_deck::{Card, Suit};
-use errors::{InvalidRoomId, PlayError, ResourceDoesNotExist, RoomJoinError, ServerFull};
-use judgment::Trick;
+use errors::{CreateRoomError, InvalidRoomId, PlayError, ResourceDoesNotExist, RoomJoinError};
+use judgment::{
+    BiddingMode, Claim, CompletedTrick, GameConfig, GameConfigError, GameResult, Phase, PlayerView,
+    StateUpdate, Teams, Trick,
+};
 use pasetors::{keys::AsymmetricKeyPair, version4::V4};
-use room::Action;
+use room::{Action, FairDealingStatus};
 use serde::{Deserialize, Serialize};
 use server::{AuthenticatedPlayer, Server};
 use tokio::sync::RwLock;
@@ -35,11 +38,18 @@ pub fn judgment_router<P: AsRef<Path>>(
         .route("/api/join", post(join))
         .route("/api/play", post(play))
         .route("/api/trick", get(trick))
+        .route("/api/completed_tricks", get(completed_tricks))
         .route("/api/predictions", get(predictions))
         .route("/api/my_hand", get(hand_of_player))
         .route("/api/scores", get(scores))
         .route("/api/round_scores", get(round_scores))
+        .route("/api/team_scores", get(team_scores))
         .route("/api/trump_suit", get(trump_suit))
+        .route("/api/state", get(turn_state))
+        .route("/api/view", get(player_view))
+        .route("/api/events", get(events))
+        .route("/api/result", get(result))
+        .route("/api/fair_dealing", get(fair_dealing))
         .fallback_service(serve_dir)
         .with_state(server.clone());
 
@@ -49,16 +59,12 @@ pub fn judgment_router<P: AsRef<Path>>(
 async fn create_room(
     State(server): State<Arc<RwLock<Server>>>,
     Json(room_request): Json<NewRoomRequest>,
-) -> Result<Json<RoomPayload>, ServerFull> {
+) -> Result<Json<RoomPayload>, CreateRoomError> {
     log::info!("received create room request");
     server
         .write()
         .await
-        .create_room(
-            room_request.players,
-            room_request.starting_hand_size,
-            room_request.decks,
-        )
+        .create_room_with_config(room_request.config()?, room_request.fair_dealing)
         .map(|room_id| Json(RoomPayload { room_id }))
 }
 
@@ -109,6 +115,27 @@ async fn trick(
     Ok(Json(trick))
 }
 
+async fn completed_tricks(
+    State(server): State<Arc<RwLock<Server>>>,
+    Query(payload): Query<RoomPayload>,
+) -> Result<Json<Vec<CompletedTrick>>, InvalidRoomId> {
+    log::info!("received completed tricks request");
+    let mut receiver = server
+        .read()
+        .await
+        .room(&payload.room_id)?
+        .completed_tricks_sender()
+        .subscribe();
+    let tricks = {
+        tokio::select! {
+            _ = receiver.changed() => (),
+            _ = tokio::time::sleep(Duration::from_secs(10)) => ()
+        };
+        receiver.borrow().clone()
+    };
+    Ok(Json(tricks))
+}
+
 async fn predictions(
     State(server): State<Arc<RwLock<Server>>>,
     Query(payload): Query<RoomPayload>,
@@ -166,6 +193,29 @@ async fn scores(
     Ok(Json(scores))
 }
 
+async fn team_scores(
+    State(server): State<Arc<RwLock<Server>>>,
+    Query(payload): Query<RoomPayload>,
+) -> Result<Json<Option<TeamScores>>, InvalidRoomId> {
+    log::info!("received team scores request");
+    let (teams, mut receiver) = {
+        let server = server.read().await;
+        let room = server.room(&payload.room_id)?;
+        (room.teams().cloned(), room.team_scores_sender().subscribe())
+    };
+    let Some(teams) = teams else {
+        return Ok(Json(None));
+    };
+    let scores = {
+        tokio::select! {
+            _ = receiver.changed() => (),
+            _ = tokio::time::sleep(Duration::from_secs(10)) => ()
+        };
+        receiver.borrow().clone()
+    };
+    Ok(Json(Some(TeamScores { teams, scores })))
+}
+
 async fn round_scores(
     State(server): State<Arc<RwLock<Server>>>,
     Query(payload): Query<RoomPayload>,
@@ -208,6 +258,112 @@ async fn trump_suit(
     Ok(Json(suit))
 }
 
+async fn result(
+    State(server): State<Arc<RwLock<Server>>>,
+    Query(payload): Query<RoomPayload>,
+) -> Result<Json<Option<GameResult>>, InvalidRoomId> {
+    log::info!("received result request");
+    let mut receiver = server
+        .read()
+        .await
+        .room(&payload.room_id)?
+        .result_sender()
+        .subscribe();
+    let result = {
+        tokio::select! {
+            _ = receiver.changed() => (),
+            _ = tokio::time::sleep(Duration::from_secs(10)) => ()
+        };
+        receiver.borrow().clone()
+    };
+    Ok(Json(result))
+}
+
+async fn fair_dealing(
+    State(server): State<Arc<RwLock<Server>>>,
+    Query(payload): Query<RoomPayload>,
+) -> Result<Json<Option<FairDealingStatus>>, InvalidRoomId> {
+    log::info!("received fair dealing request");
+    Ok(Json(
+        server.read().await.room(&payload.room_id)?.fair_dealing(),
+    ))
+}
+
+async fn turn_state(
+    player: AuthenticatedPlayer,
+    State(server): State<Arc<RwLock<Server>>>,
+) -> Result<Json<TurnState>, ResourceDoesNotExist> {
+    log::info!("received state request from player {}", player.player_id);
+    let server = server.read().await;
+    let room = server.room(&player.room_id)?;
+    Ok(Json(TurnState {
+        phase: room.phase(),
+        current_player: room.current_player(),
+        legal_cards: room.legal_cards(player.player_id)?,
+        legal_predictions: room.legal_predictions(player.player_id)?,
+        claim: room.claim().cloned(),
+    }))
+}
+
+async fn player_view(
+    player: AuthenticatedPlayer,
+    State(server): State<Arc<RwLock<Server>>>,
+) -> Result<Json<PlayerView>, ResourceDoesNotExist> {
+    log::info!("received view request from player {}", player.player_id);
+    Ok(Json(
+        server
+            .read()
+            .await
+            .room(&player.room_id)?
+            .view_for(player.player_id)?,
+    ))
+}
+
+async fn events(
+    State(server): State<Arc<RwLock<Server>>>,
+    Query(payload): Query<EventsPayload>,
+) -> Result<Json<Events>, InvalidRoomId> {
+    log::info!("received events request");
+    let mut receiver = server
+        .read()
+        .await
+        .room(&payload.room_id)?
+        .events_sender()
+        .subscribe();
+    let seen = *receiver.borrow();
+    if seen.generation == payload.generation && seen.events <= payload.since {
+        tokio::select! {
+            _ = receiver.changed() => (),
+            _ = tokio::time::sleep(Duration::from_secs(10)) => ()
+        };
+    }
+    let server = server.read().await;
+    let room = server.room(&payload.room_id)?;
+    let generation = room.events_sender().borrow().generation;
+    // the caller read events that have since been undone
+    let since = if generation == payload.generation {
+        payload.since
+    } else {
+        0
+    };
+    let events = room.events_since(since).to_vec();
+    Ok(Json(Events {
+        generation,
+        next: since + events.len(),
+        events,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+struct TurnState {
+    phase: Phase,
+    current_player: Option<usize>,
+    legal_cards: Vec<Card>,
+    legal_predictions: Vec<u8>,
+    /// The claim waiting for the claimant's opponents, if any.
+    claim: Option<Claim>,
+}
+
 #[derive(Debug, Serialize)]
 struct JoinSuccess {
     token_type: String,
@@ -219,9 +375,65 @@ struct RoomPayload {
     room_id: Uuid,
 }
 
+#[derive(Debug, Deserialize)]
+struct EventsPayload {
+    room_id: Uuid,
+    /// Number of events the caller has already seen.
+    #[serde(default)]
+    since: usize,
+    /// Generation of the event log the caller has read, see
+    /// [`EventCount::generation`](room::EventCount::generation).
+    #[serde(default)]
+    generation: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Events {
+    /// The `generation` to ask for next. When it changes, `events` starts
+    /// over from the first event of the game.
+    generation: u64,
+    /// The `since` to ask for next.
+    next: usize,
+    events: Vec<StateUpdate>,
+}
+
+/// The score of every team, along with who plays in which team.
+#[derive(Debug, Serialize)]
+struct TeamScores {
+    teams: Teams,
+    scores: Vec<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct NewRoomRequest {
     players: u8,
     starting_hand_size: u8,
     decks: u8,
+    #[serde(default)]
+    bidding: BiddingMode,
+    /// Allows blind predictions scoring this many times the points.
+    #[serde(default)]
+    blind_multiplier: Option<u8>,
+    #[serde(default)]
+    teams: Option<Teams>,
+    /// Lets the players take part in shuffling every deal, see
+    /// [`room::Room::with_fair_dealing`].
+    #[serde(default)]
+    fair_dealing: bool,
+}
+
+impl NewRoomRequest {
+    /// The rules of the requested room.
+    fn config(&self) -> Result<GameConfig, GameConfigError> {
+        let mut builder = GameConfig::builder(self.players, self.starting_hand_size)
+            .decks(self.decks)
+            .bidding(self.bidding);
+        if let Some(multiplier) = self.blind_multiplier {
+            builder = builder.blind_predictions(multiplier);
+        }
+        if let Some(teams) = &self.teams {
+            builder = builder.teams(teams.clone());
+        }
+        builder.build()
+    }
 }
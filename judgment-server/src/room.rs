@@ -1,10 +1,14 @@
 use card_deck::standard_deck::{Card, Suit};
-use judgment::{InvalidTransition, Judgment, StateUpdate, Transition, Trick};
+use judgment::{
+    commitment, Claim, CompletedTrick, DealProof, GameConfig, GameConfigError, GameResult,
+    InvalidTransition, Judgment, Phase, PlayerView, StateUpdate, Teams, Transition, Trick,
+    UnfairDeal,
+};
 use pasetors::claims::Claims;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 
-use crate::errors::{InvalidPlayerId, RoomFull};
+use crate::errors::{FairDealError, InvalidPlayerId, PlayError, RoomFull};
 
 #[derive(Debug)]
 pub struct Room {
@@ -12,34 +16,86 @@ pub struct Room {
     game: Judgment,
     max_players: u8,
     trick_sender: watch::Sender<Trick>,
+    completed_tricks_sender: watch::Sender<Vec<CompletedTrick>>,
     predictions_sender: watch::Sender<Vec<Option<u8>>>,
     round_scores_sender: watch::Sender<Vec<u8>>,
     game_scores_sender: watch::Sender<Vec<i64>>,
+    team_scores_sender: watch::Sender<Vec<i64>>,
     trump_suit_sender: watch::Sender<Option<Suit>>,
+    result_sender: watch::Sender<Option<GameResult>>,
+    undo_votes: Vec<bool>,
+    events: Vec<StateUpdate>,
+    /// The length of `events` before each transition of the game's history.
+    event_marks: Vec<usize>,
+    events_sender: watch::Sender<EventCount>,
+    /// The proofs of the deals so far and the deal waiting for commitments
+    /// and reveals, if the room deals with commit-reveal.
+    fair_dealing: Option<FairDealing>,
 }
 
 impl Room {
     /// Create a new room that can accommodate given amount of players and card
     /// decks.
-    pub fn new(players: u8, starting_hand_size: u8, decks: u8) -> Self {
-        let game = Judgment::new(players, starting_hand_size, Some(decks));
+    ///
+    /// Errors if the game cannot be played with the given parameters.
+    pub fn new(players: u8, starting_hand_size: u8, decks: u8) -> Result<Self, GameConfigError> {
+        GameConfig::builder(players, starting_hand_size)
+            .decks(decks)
+            .build()
+            .map(Room::with_config)
+    }
+
+    /// Create a new room for a game played with the rules in `config`.
+    pub fn with_config(config: GameConfig) -> Self {
+        let players = config.players();
+        let game = Judgment::with_config(config);
         let (trick_sender, _) = watch::channel(game.trick().clone());
+        let (completed_tricks_sender, _) = watch::channel(Vec::new());
         let (predictions_sender, _) = watch::channel(Vec::new());
         let (round_scores_sender, _) = watch::channel(Vec::new());
         let (game_scores_sender, _) = watch::channel(Vec::new());
+        let (team_scores_sender, _) =
+            watch::channel(game.team_scores().unwrap_or_default().to_vec());
         let (trump_suit_sender, _) = watch::channel(None);
+        let (result_sender, _) = watch::channel(None);
+        let (events_sender, _) = watch::channel(EventCount::default());
         Room {
             joined_players: 0,
             game,
             max_players: players,
             trick_sender,
+            completed_tricks_sender,
             predictions_sender,
             round_scores_sender,
             game_scores_sender,
+            team_scores_sender,
             trump_suit_sender,
+            result_sender,
+            undo_votes: vec![false; usize::from(players)],
+            events: Vec::new(),
+            event_marks: Vec::new(),
+            events_sender,
+            fair_dealing: None,
         }
     }
 
+    /// Create a new room for a game played with the rules in `config`, where
+    /// the players take part in the shuffle of every deal.
+    ///
+    /// Before every deal the room commits to a secret seed, and every player
+    /// commits to their own secret entropy with [`Action::CommitEntropy`].
+    /// Once all commitments are in, the players reveal their entropy with
+    /// [`Action::RevealEntropy`], and the last reveal deals the cards with
+    /// the seed combined from all of them. [`Action::Deal`] is not used.
+    pub fn with_fair_dealing(config: GameConfig) -> Self {
+        let mut room = Room::with_config(config);
+        room.fair_dealing = Some(FairDealing {
+            proofs: Vec::new(),
+            pending: None,
+        });
+        room
+    }
+
     /// Try to join the room.
     pub fn join(&mut self) -> Result<Claims, RoomFull> {
         if self.is_full() {
@@ -54,8 +110,14 @@ impl Room {
             self.game.start().unwrap();
             self.trump_suit_sender
                 .send_replace(self.game.trump_suit().cloned());
-            self.play(Action::Deal, usize::from(self.max_players))
-                .unwrap();
+            // leave the first deal to the players when they may want to
+            // predict blind before it, or have to take part in it
+            if self.fair_dealing.is_some() {
+                self.prepare_fair_deal();
+            } else if self.game.config().blind_multiplier().is_none() {
+                self.play(Action::Deal, usize::from(self.max_players))
+                    .unwrap();
+            }
         }
         Ok(claim)
     }
@@ -66,76 +128,210 @@ impl Room {
     }
 
     /// Attempt to play a card.
-    pub fn play(&mut self, action: Action, player: usize) -> Result<(), InvalidTransition> {
+    pub fn play(&mut self, action: Action, player: usize) -> Result<(), PlayError> {
         if !self.is_full() {
-            return Err(InvalidTransition::OutOfTurnPlay);
-        }
-        match action {
-            Action::Play(card) => {
-                let transition = Transition::Play { player, card };
-                let updates = self.game.update(transition)?;
-                assert!(
-                    !updates.is_empty() && updates.len() <= 3,
-                    "a Transition::Play should create 1..=3 updates"
-                );
-                for update in updates {
-                    match update {
-                        StateUpdate::Trick(trick) => {
-                            self.trick_sender.send_replace(trick);
-                        }
-                        StateUpdate::RoundScores(scores) => {
-                            self.round_scores_sender.send_replace(scores);
-                        }
-                        StateUpdate::GameScores(scores) => {
-                            self.game_scores_sender.send_replace(scores);
-                            self.trump_suit_sender
-                                .send_replace(self.game.trump_suit().cloned());
-                        }
-                        StateUpdate::CardsDealt | StateUpdate::Predictions(_) => {
-                            unreachable!("a Transition::Play should never result in this state")
+            return Err(InvalidTransition::OutOfTurnPlay.into());
+        }
+        let transition = match action {
+            Action::Play(card) => Transition::Play { player, card },
+            Action::PredictScore(score) => Transition::PredictScore { player, score },
+            Action::PredictBlind(score) => Transition::PredictBlind { player, score },
+            Action::Claim => Transition::Claim { player },
+            Action::AcceptClaim => Transition::AcceptClaim { player },
+            Action::RejectClaim => Transition::RejectClaim { player },
+            Action::Deal => {
+                if self.fair_dealing.is_some() {
+                    return Err(FairDealError::DealIsAutomatic.into());
+                }
+                Transition::Deal {
+                    seed: rand::random(),
+                }
+            }
+            Action::CommitEntropy(entropy_commitment) => {
+                self.pending_deal()?.commit(player, entropy_commitment)?;
+                return Ok(());
+            }
+            Action::RevealEntropy(entropy) => {
+                match self.pending_deal()?.reveal(player, entropy)? {
+                    Some(proof) => {
+                        let seed = proof.seed();
+                        self.game.validate(&Transition::Deal { seed })?;
+                        if let Some(fair_dealing) = &mut self.fair_dealing {
+                            fair_dealing.proofs.push(proof);
+                            fair_dealing.pending = None;
                         }
+                        Transition::Deal { seed }
                     }
+                    None => return Ok(()),
                 }
-                Ok(())
             }
-            Action::PredictScore(score) => {
-                let transition = Transition::PredictScore { player, score };
-                let updates = self.game.update(transition)?;
-                assert_eq!(
-                    updates.len(),
-                    1,
-                    "a Transition::PredictScore should create exactly 1 update"
-                );
-                for update in updates {
-                    match update {
-                        StateUpdate::Predictions(predictions) => {
-                            self.predictions_sender.send_replace(predictions);
+            Action::Undo => {
+                if let Some(vote) = self.undo_votes.get_mut(player) {
+                    *vote = true;
+                }
+                if self.undo_votes.iter().all(|vote| *vote) {
+                    self.game.undo()?;
+                    self.undo_votes.iter_mut().for_each(|vote| *vote = false);
+                    // take the events of the undone move back out of the log
+                    if let Some(mark) = self.event_marks.pop() {
+                        self.events.truncate(mark);
+                    }
+                    self.events_sender.send_modify(|count| {
+                        count.generation += 1;
+                        count.events = self.events.len();
+                    });
+                    // forget the proof of an undone deal
+                    let deals = self
+                        .game
+                        .history()
+                        .iter()
+                        .filter(|transition| matches!(transition, Transition::Deal { .. }))
+                        .count();
+                    if let Some(fair_dealing) = &mut self.fair_dealing {
+                        fair_dealing.proofs.truncate(deals);
+                        if self.game.phase() != Phase::Dealing {
+                            fair_dealing.pending = None;
                         }
-                        _ => unreachable!(
-                            "a Transition::PredictScore should never result in this state"
-                        ),
                     }
+                    self.prepare_fair_deal();
+                    self.publish_state();
                 }
-                Ok(())
+                return Ok(());
             }
-            Action::Deal => {
-                let seed = rand::random();
-                let transition = Transition::Deal { seed };
-                let updates = self.game.update(transition)?;
-                assert_eq!(
-                    updates.len(),
-                    1,
-                    "a Transition::Deal should create exactly 1 update"
-                );
-                for update in updates {
-                    match update {
-                        StateUpdate::CardsDealt => (),
-                        _ => unreachable!("a Transition::Deal should never result in this state"),
-                    }
+        };
+        let updates = self.game.update(transition)?;
+        // votes are for undoing the move before this one
+        self.undo_votes.iter_mut().for_each(|vote| *vote = false);
+        for update in &updates {
+            match update {
+                StateUpdate::Trick(trick) => {
+                    self.trick_sender.send_replace(trick.clone());
                 }
-                Ok(())
+                StateUpdate::Predictions(predictions) => {
+                    self.predictions_sender.send_replace(predictions.clone());
+                }
+                StateUpdate::RoundScores(scores) => {
+                    self.round_scores_sender.send_replace(scores.clone());
+                }
+                StateUpdate::GameScores(scores) => {
+                    self.game_scores_sender.send_replace(scores.clone());
+                }
+                StateUpdate::TeamScores(scores) => {
+                    self.team_scores_sender.send_replace(scores.clone());
+                }
+                StateUpdate::TrickWon { .. } => {
+                    self.completed_tricks_sender.send_replace(
+                        self.game
+                            .completed_tricks()
+                            .map(|tricks| tricks.to_vec())
+                            .unwrap_or_default(),
+                    );
+                }
+                StateUpdate::RoundStarted { trump, .. } => {
+                    self.trump_suit_sender.send_replace(*trump);
+                    self.completed_tricks_sender.send_replace(Vec::new());
+                    // clear the last round's predictions, keeping any blind
+                    // ones that are not sealed
+                    self.predictions_sender.send_replace(
+                        self.game
+                            .predicted_scores()
+                            .map(|predictions| predictions.to_vec())
+                            .unwrap_or_default(),
+                    );
+                }
+                StateUpdate::GameOver { .. } => {
+                    self.result_sender.send_replace(self.game.result());
+                }
+                // sealed predictions only reach the predictions channel
+                // once they are revealed
+                StateUpdate::CardsDealt
+                | StateUpdate::PredictionSealed { .. }
+                | StateUpdate::RoundEnded { .. }
+                | StateUpdate::TrumpTurnedUp(_)
+                | StateUpdate::TurnChanged(_)
+                | StateUpdate::ClaimMade { .. }
+                | StateUpdate::ClaimAccepted { .. }
+                | StateUpdate::ClaimRejected { .. } => (),
             }
         }
+        self.event_marks.push(self.events.len());
+        self.events.extend(updates);
+        self.events_sender
+            .send_modify(|count| count.events = self.events.len());
+        self.prepare_fair_deal();
+        Ok(())
+    }
+
+    /// Commit to a new secret seed if the game waits for a deal that has not
+    /// been prepared yet and the room deals with commit-reveal.
+    fn prepare_fair_deal(&mut self) {
+        let players = usize::from(self.max_players);
+        if self.game.phase() != Phase::Dealing {
+            return;
+        }
+        if let Some(fair_dealing) = &mut self.fair_dealing {
+            fair_dealing
+                .pending
+                .get_or_insert_with(|| PendingDeal::new(rand::random(), players));
+        }
+    }
+
+    /// The deal waiting for commitments and reveals.
+    fn pending_deal(&mut self) -> Result<&mut PendingDeal, FairDealError> {
+        self.fair_dealing
+            .as_mut()
+            .ok_or(FairDealError::NotEnabled)?
+            .pending
+            .as_mut()
+            .ok_or(FairDealError::NoPendingDeal)
+    }
+
+    /// Get the commitments of the next deal and the proofs of the deals so
+    /// far, or `None` if the room does not deal with commit-reveal.
+    pub fn fair_dealing(&self) -> Option<FairDealingStatus> {
+        let fair_dealing = self.fair_dealing.as_ref()?;
+        Some(FairDealingStatus {
+            pending: fair_dealing
+                .pending
+                .as_ref()
+                .map(|pending| PendingCommitments {
+                    dealer_commitment: pending.dealer_commitment,
+                    player_commitments: pending.player_commitments.clone(),
+                    revealed: pending.player_entropy.iter().map(Option::is_some).collect(),
+                }),
+            proofs: fair_dealing.proofs.clone(),
+            verification: self.game.verify_deals(&fair_dealing.proofs).err(),
+        })
+    }
+
+    /// Send the current state of the game through all the notifier channels.
+    fn publish_state(&self) {
+        self.trick_sender.send_replace(self.game.trick().clone());
+        self.completed_tricks_sender.send_replace(
+            self.game
+                .completed_tricks()
+                .map(|tricks| tricks.to_vec())
+                .unwrap_or_default(),
+        );
+        self.predictions_sender.send_replace(
+            self.game
+                .predicted_scores()
+                .map(|predictions| predictions.to_vec())
+                .unwrap_or_default(),
+        );
+        self.round_scores_sender.send_replace(
+            self.game
+                .round_scores()
+                .map(|scores| scores.to_vec())
+                .unwrap_or_default(),
+        );
+        self.game_scores_sender
+            .send_replace(self.game.scores().to_vec());
+        self.team_scores_sender
+            .send_replace(self.game.team_scores().unwrap_or_default().to_vec());
+        self.trump_suit_sender
+            .send_replace(self.game.trump_suit().cloned());
+        self.result_sender.send_replace(self.game.result());
     }
 
     /// Get the hand of a player.
@@ -143,11 +339,49 @@ impl Room {
         self.game.hand_of_player(player).ok_or(InvalidPlayerId)
     }
 
+    /// Get everything a player is allowed to see about the game.
+    pub fn view_for(&self, player: usize) -> Result<PlayerView, InvalidPlayerId> {
+        self.game.view_for(player).ok_or(InvalidPlayerId)
+    }
+
+    /// Get the stage the game is in.
+    pub fn phase(&self) -> Phase {
+        self.game.phase()
+    }
+
+    /// Get the player whose turn it is, if any.
+    pub fn current_player(&self) -> Option<usize> {
+        self.game.current_player()
+    }
+
+    /// Get the cards that a player is allowed to play right now.
+    pub fn legal_cards(&self, player: usize) -> Result<Vec<Card>, InvalidPlayerId> {
+        self.hand_of_player(player)?;
+        Ok(self.game.legal_cards(player))
+    }
+
+    /// Get the scores that a player is allowed to predict right now.
+    pub fn legal_predictions(&self, player: usize) -> Result<Vec<u8>, InvalidPlayerId> {
+        self.hand_of_player(player)?;
+        Ok(self.game.legal_predictions(player))
+    }
+
+    /// Get the claim waiting for the claimant's opponents, if any.
+    pub fn claim(&self) -> Option<&Claim> {
+        self.game.claim()
+    }
+
     /// Get the notifier channel that communicates when the trick changes.
     pub fn trick_sender(&self) -> &watch::Sender<Trick> {
         &self.trick_sender
     }
 
+    /// Get the notifier channel that communicates when a trick of the current
+    /// round is completed, or a new round starts without any.
+    pub fn completed_tricks_sender(&self) -> &watch::Sender<Vec<CompletedTrick>> {
+        &self.completed_tricks_sender
+    }
+
     /// Get the notifier channel that communicates when the predictions change.
     pub fn predictions_sender(&self) -> &watch::Sender<Vec<Option<u8>>> {
         &self.predictions_sender
@@ -163,6 +397,17 @@ impl Room {
         &self.game_scores_sender
     }
 
+    /// Get the notifier channel that communicates when the team scores
+    /// change. Never changes unless the players play in teams.
+    pub fn team_scores_sender(&self) -> &watch::Sender<Vec<i64>> {
+        &self.team_scores_sender
+    }
+
+    /// Get the teams the players are split into, if they play in teams.
+    pub fn teams(&self) -> Option<&Teams> {
+        self.game.config().teams()
+    }
+
     /// Check whether the game is over.
     pub fn is_game_over(&self) -> bool {
         self.game.is_over()
@@ -171,30 +416,504 @@ impl Room {
     pub fn trump_suit_sender(&self) -> &watch::Sender<Option<Suit>> {
         &self.trump_suit_sender
     }
+
+    /// Get the notifier channel that communicates the result of the game once
+    /// it is over.
+    pub fn result_sender(&self) -> &watch::Sender<Option<GameResult>> {
+        &self.result_sender
+    }
+
+    /// Get every event of the game since the `since`th one.
+    ///
+    /// Undoing a move removes the events it caused from the log and starts a
+    /// new [`EventCount::generation`] of it.
+    pub fn events_since(&self, since: usize) -> &[StateUpdate] {
+        self.events.get(since..).unwrap_or_default()
+    }
+
+    /// Get the notifier channel that communicates the size of the event log.
+    pub fn events_sender(&self) -> &watch::Sender<EventCount> {
+        &self.events_sender
+    }
 }
 
 /// An action that a player can take; either play a card or pass their turn.
+///
+/// [`Action::PredictBlind`] predicts before the cards of the round are dealt,
+/// if the room's rules allow it.
+///
+/// [`Action::Claim`] claims all the tricks left in the round instead of
+/// leading, and the claimant's opponents answer it with
+/// [`Action::AcceptClaim`] or [`Action::RejectClaim`].
+///
+/// [`Action::Undo`] is a vote to take back the last move. It only takes effect
+/// once every player in the room has voted for it. The pending votes are
+/// dropped once another move of the game is accepted, such as the deal made
+/// by the last [`Action::RevealEntropy`]; rejected moves and the other
+/// entropy actions leave them in place.
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum Action {
     Play(Card),
     PredictScore(u8),
+    PredictBlind(u8),
+    Claim,
+    AcceptClaim,
+    RejectClaim,
     Deal,
+    Undo,
+    /// Commit to secret entropy for the next deal, in a room with fair
+    /// dealing; see [`judgment::commitment`].
+    CommitEntropy([u8; 32]),
+    /// Reveal the entropy committed to, once every player has committed.
+    RevealEntropy([u8; 32]),
+}
+
+#[derive(Debug)]
+struct FairDealing {
+    proofs: Vec<DealProof>,
+    pending: Option<PendingDeal>,
+}
+
+/// A deal collecting the commitments and then the entropy of the players.
+#[derive(Debug)]
+struct PendingDeal {
+    dealer_seed: [u8; 32],
+    dealer_commitment: [u8; 32],
+    player_commitments: Vec<Option<[u8; 32]>>,
+    player_entropy: Vec<Option<[u8; 32]>>,
+}
+
+impl PendingDeal {
+    fn new(dealer_seed: [u8; 32], players: usize) -> Self {
+        PendingDeal {
+            dealer_seed,
+            dealer_commitment: commitment(&dealer_seed),
+            player_commitments: vec![None; players],
+            player_entropy: vec![None; players],
+        }
+    }
+
+    fn commit(&mut self, player: usize, entropy_commitment: [u8; 32]) -> Result<(), FairDealError> {
+        let committed = self
+            .player_commitments
+            .get_mut(player)
+            .ok_or(FairDealError::InvalidPlayer)?;
+        if committed.is_some() {
+            return Err(FairDealError::AlreadyCommitted);
+        }
+        *committed = Some(entropy_commitment);
+        Ok(())
+    }
+
+    /// Record the entropy of `player`, and return the proof of the deal once
+    /// everyone has revealed theirs.
+    fn reveal(
+        &mut self,
+        player: usize,
+        entropy: [u8; 32],
+    ) -> Result<Option<DealProof>, FairDealError> {
+        let player_commitments: Option<Vec<[u8; 32]>> =
+            self.player_commitments.iter().copied().collect();
+        let Some(player_commitments) = player_commitments else {
+            return Err(FairDealError::CommitmentsMissing);
+        };
+        let revealed = self
+            .player_entropy
+            .get_mut(player)
+            .ok_or(FairDealError::InvalidPlayer)?;
+        if revealed.is_some() {
+            return Err(FairDealError::AlreadyRevealed);
+        }
+        if commitment(&entropy) != player_commitments[player] {
+            return Err(FairDealError::RevealMismatch);
+        }
+        *revealed = Some(entropy);
+        Ok(self
+            .player_entropy
+            .iter()
+            .copied()
+            .collect::<Option<Vec<_>>>()
+            .map(|player_entropy| DealProof {
+                dealer_commitment: self.dealer_commitment,
+                dealer_seed: self.dealer_seed,
+                player_commitments,
+                player_entropy,
+            }))
+    }
+}
+
+/// Where commit-reveal dealing stands in a room.
+#[derive(Debug, Clone, Serialize)]
+pub struct FairDealingStatus {
+    /// The commitments of the next deal, while it waits for the players.
+    pub pending: Option<PendingCommitments>,
+    /// Everything needed to check the deals so far, in order.
+    pub proofs: Vec<DealProof>,
+    /// Why the deals could not be verified, `None` if they were.
+    pub verification: Option<UnfairDeal>,
+}
+
+/// The size of the event log of a room.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventCount {
+    /// Bumped whenever an undo takes events back out of the log, so that
+    /// anyone who read them knows to read the log again from the start.
+    pub generation: u64,
+    pub events: usize,
+}
+
+/// The commitments made so far for the next deal.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingCommitments {
+    pub dealer_commitment: [u8; 32],
+    pub player_commitments: Vec<Option<[u8; 32]>>,
+    /// Whether every player has revealed their entropy.
+    pub revealed: Vec<bool>,
 }
 
 #[cfg(test)]
 mod tests {
+    use judgment::{
+        commitment, BiddingMode, GameConfig, GameConfigError, InvalidTransition, Phase,
+        StateUpdate, TeamBid, Teams, Transition,
+    };
     use pasetors::claims::Claims;
 
-    use crate::errors::RoomFull;
+    use crate::errors::{FairDealError, PlayError, RoomFull};
 
-    use super::Room;
+    use super::{Action, EventCount, Room};
 
     #[test]
     fn test_room_joining() {
-        let mut room = Room::new(2, 2, 1);
+        let mut room = Room::new(2, 2, 1).unwrap();
         for _ in 0..2 {
             assert!(matches!(room.join(), Ok(Claims { .. })));
         }
         assert!(matches!(room.join(), Err(RoomFull { .. })));
     }
+
+    #[test]
+    fn test_invalid_room() {
+        assert_eq!(
+            Room::new(0, 2, 1).unwrap_err(),
+            GameConfigError::ZeroPlayers
+        );
+        assert!(matches!(
+            Room::new(5, 13, 1),
+            Err(GameConfigError::TooFewDecks { .. })
+        ));
+    }
+
+    #[test]
+    fn test_undo_needs_every_vote() {
+        let mut room = Room::new(2, 2, 1).unwrap();
+        for _ in 0..2 {
+            room.join().unwrap();
+        }
+        let prediction = Action::PredictScore(0);
+        room.play(prediction, 0).unwrap();
+        assert_eq!(room.predictions_sender().borrow().clone(), [Some(0), None]);
+        room.play(Action::Undo, 0).unwrap();
+        assert_eq!(room.predictions_sender().borrow().clone(), [Some(0), None]);
+        // a rejected move does not take back the votes
+        assert!(room.play(Action::PredictScore(0), 0).is_err());
+        room.play(Action::Undo, 1).unwrap();
+        assert_eq!(room.predictions_sender().borrow().clone(), [None, None]);
+
+        // but an accepted one does
+        room.play(prediction, 0).unwrap();
+        room.play(Action::Undo, 0).unwrap();
+        room.play(Action::PredictScore(1), 1).unwrap();
+        room.play(Action::Undo, 1).unwrap();
+        assert_eq!(
+            room.predictions_sender().borrow().clone(),
+            [Some(0), Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_events_are_logged() {
+        let mut room = Room::new(2, 1, 1).unwrap();
+        for _ in 0..2 {
+            room.join().unwrap();
+        }
+        assert!(matches!(
+            room.events_since(0),
+            [
+                StateUpdate::RoundStarted { hand_size: 1, .. },
+                StateUpdate::CardsDealt,
+                StateUpdate::TurnChanged(0)
+            ]
+        ));
+        assert_eq!(
+            *room.events_sender().borrow(),
+            EventCount {
+                generation: 0,
+                events: 3
+            }
+        );
+        room.play(Action::PredictScore(0), 0).unwrap();
+        assert_eq!(
+            room.events_since(3),
+            [
+                StateUpdate::Predictions(vec![Some(0), None]),
+                StateUpdate::TurnChanged(1)
+            ]
+        );
+        assert!(room.events_since(10).is_empty());
+
+        // an undo takes the events of the move back
+        room.play(Action::Undo, 0).unwrap();
+        room.play(Action::Undo, 1).unwrap();
+        assert_eq!(
+            *room.events_sender().borrow(),
+            EventCount {
+                generation: 1,
+                events: 3
+            }
+        );
+        assert!(room.events_since(3).is_empty());
+        room.play(Action::PredictScore(1), 0).unwrap();
+        assert_eq!(
+            room.events_since(3),
+            [
+                StateUpdate::Predictions(vec![Some(1), None]),
+                StateUpdate::TurnChanged(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sealed_predictions_do_not_leak() {
+        let config = GameConfig::builder(3, 3)
+            .bidding(BiddingMode::Sealed)
+            .blind_predictions(2)
+            .build()
+            .unwrap();
+        let mut room = Room::with_config(config);
+        for _ in 0..3 {
+            room.join().unwrap();
+        }
+        // the players get to predict blind before the first deal
+        assert_eq!(room.phase(), Phase::Dealing);
+        room.play(Action::PredictBlind(3), 1).unwrap();
+        room.play(Action::Deal, 0).unwrap();
+        room.play(Action::PredictScore(2), 0).unwrap();
+        assert_eq!(room.view_for(0).unwrap().sealed_prediction, Some(2));
+        let other = room.view_for(1).unwrap();
+        assert_eq!(other.sealed_prediction, Some(3));
+        assert_eq!(other.predictions, [None, None, None]);
+        assert_eq!(other.predicted, [true, true, false]);
+        assert_eq!(
+            room.predictions_sender().borrow().clone(),
+            [None, None, None]
+        );
+        assert!(!room
+            .events_since(0)
+            .iter()
+            .any(|event| matches!(event, StateUpdate::Predictions(_))));
+        room.play(Action::PredictScore(0), 2).unwrap();
+        assert_eq!(
+            room.predictions_sender().borrow().clone(),
+            [Some(2), Some(3), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_completed_tricks_are_published() {
+        let mut room = Room::new(2, 2, 1).unwrap();
+        for _ in 0..2 {
+            room.join().unwrap();
+        }
+        room.play(Action::PredictScore(0), 0).unwrap();
+        room.play(Action::PredictScore(0), 1).unwrap();
+        let mut played = Vec::new();
+        for _ in 0..2 {
+            assert!(room.completed_tricks_sender().borrow().is_empty());
+            let player = room.current_player().unwrap();
+            let card = room.legal_cards(player).unwrap()[0];
+            room.play(Action::Play(card), player).unwrap();
+            played.push((player, card));
+        }
+        let tricks = room.completed_tricks_sender().borrow().clone();
+        assert_eq!(tricks.len(), 1);
+        assert_eq!(tricks[0].leader, 0);
+        assert_eq!(tricks[0].cards, played);
+        let winner = tricks[0].winner;
+        // taking the trick back clears it again
+        room.play(Action::Undo, 0).unwrap();
+        room.play(Action::Undo, 1).unwrap();
+        assert!(room.completed_tricks_sender().borrow().is_empty());
+        room.play(Action::Play(played[1].1), played[1].0).unwrap();
+        assert_eq!(room.completed_tricks_sender().borrow().len(), 1);
+        assert_eq!(room.current_player(), Some(winner));
+        for _ in 0..2 {
+            let player = room.current_player().unwrap();
+            let card = room.legal_cards(player).unwrap()[0];
+            room.play(Action::Play(card), player).unwrap();
+        }
+        // the next round starts without any
+        assert!(room.completed_tricks_sender().borrow().is_empty());
+    }
+
+    #[test]
+    fn test_claims_end_the_round() {
+        let mut room = Room::new(3, 2, 1).unwrap();
+        for _ in 0..3 {
+            room.join().unwrap();
+        }
+        for player in 0..3 {
+            let score = room.legal_predictions(player).unwrap()[0];
+            room.play(Action::PredictScore(score), player).unwrap();
+        }
+        room.play(Action::Claim, 0).unwrap();
+        assert_eq!(room.phase(), Phase::Claiming);
+        assert_eq!(room.claim().unwrap().player, 0);
+        assert!(matches!(
+            room.play(Action::AcceptClaim, 0),
+            Err(PlayError::InvalidTransition(
+                InvalidTransition::NotAnOpponent
+            ))
+        ));
+        room.play(Action::AcceptClaim, 1).unwrap();
+        room.play(Action::AcceptClaim, 2).unwrap();
+        assert_eq!(room.phase(), Phase::Dealing);
+        assert!(room.claim().is_none());
+        let scores = room.game_scores_sender().borrow().clone();
+        assert_eq!(scores.len(), 3);
+        assert!(room
+            .events_since(0)
+            .iter()
+            .any(|event| matches!(event, StateUpdate::ClaimAccepted { player: 2 })));
+    }
+
+    #[test]
+    fn test_team_scores_are_published() {
+        let config = GameConfig::builder(4, 1)
+            .teams(Teams::new(vec![0, 1, 0, 1], TeamBid::Joint))
+            .build()
+            .unwrap();
+        let mut room = Room::with_config(config);
+        for _ in 0..4 {
+            room.join().unwrap();
+        }
+        assert_eq!(room.team_scores_sender().borrow().clone(), [0, 0]);
+        room.play(Action::PredictScore(1), 0).unwrap();
+        room.play(Action::PredictScore(1), 1).unwrap();
+        for _ in 0..4 {
+            let player = room.current_player().unwrap();
+            let card = room.legal_cards(player).unwrap()[0];
+            room.play(Action::Play(card), player).unwrap();
+        }
+        // exactly one team took the only trick and made its prediction
+        let mut scores = room.team_scores_sender().borrow().clone();
+        scores.sort();
+        assert_eq!(scores, [-1, 1]);
+        assert_eq!(
+            room.result_sender().borrow().as_ref().unwrap().team_scores,
+            *room.team_scores_sender().borrow()
+        );
+    }
+
+    #[test]
+    fn test_fair_dealing() {
+        let mut room = Room::with_fair_dealing(GameConfig::builder(2, 1).build().unwrap());
+        let fair_error = |result| match result {
+            Err(PlayError::FairDeal(err)) => err,
+            other => panic!("expected a fair dealing error, got {other:?}"),
+        };
+        for _ in 0..2 {
+            room.join().unwrap();
+        }
+        assert_eq!(room.phase(), Phase::Dealing);
+        let dealer_commitment = room
+            .fair_dealing()
+            .unwrap()
+            .pending
+            .unwrap()
+            .dealer_commitment;
+        assert!(matches!(
+            fair_error(room.play(Action::Deal, 0)),
+            FairDealError::DealIsAutomatic
+        ));
+        let entropy = [[3; 32], [4; 32]];
+        assert!(matches!(
+            fair_error(room.play(Action::RevealEntropy(entropy[0]), 0)),
+            FairDealError::CommitmentsMissing
+        ));
+        room.play(Action::CommitEntropy(commitment(&entropy[0])), 0)
+            .unwrap();
+        assert!(matches!(
+            fair_error(room.play(Action::CommitEntropy(commitment(&entropy[0])), 0)),
+            FairDealError::AlreadyCommitted
+        ));
+        room.play(Action::CommitEntropy(commitment(&entropy[1])), 1)
+            .unwrap();
+        assert!(matches!(
+            fair_error(room.play(Action::RevealEntropy([5; 32]), 0)),
+            FairDealError::RevealMismatch
+        ));
+        room.play(Action::RevealEntropy(entropy[0]), 0).unwrap();
+        assert!(matches!(
+            fair_error(room.play(Action::RevealEntropy(entropy[0]), 0)),
+            FairDealError::AlreadyRevealed
+        ));
+        let status = room.fair_dealing().unwrap();
+        assert_eq!(status.pending.unwrap().revealed, [true, false]);
+        assert!(status.proofs.is_empty());
+        assert_eq!(room.phase(), Phase::Dealing);
+
+        // the last reveal deals the cards
+        room.play(Action::RevealEntropy(entropy[1]), 1).unwrap();
+        assert_eq!(room.phase(), Phase::Predicting);
+        let status = room.fair_dealing().unwrap();
+        assert!(status.pending.is_none());
+        assert!(status.verification.is_none());
+        let proof = &status.proofs[0];
+        assert_eq!(proof.dealer_commitment, dealer_commitment);
+        assert_eq!(proof.player_entropy, entropy);
+        assert_eq!(
+            room.game.history(),
+            [Transition::Deal { seed: proof.seed() }]
+        );
+
+        // undoing the deal starts over with a new seed
+        room.play(Action::Undo, 0).unwrap();
+        room.play(Action::Undo, 1).unwrap();
+        assert_eq!(room.phase(), Phase::Dealing);
+        let status = room.fair_dealing().unwrap();
+        assert!(status.proofs.is_empty());
+        let pending = status.pending.unwrap();
+        assert_ne!(pending.dealer_commitment, dealer_commitment);
+        assert_eq!(pending.player_commitments, [None, None]);
+
+        let mut unfair = Room::new(2, 1, 1).unwrap();
+        unfair.join().unwrap();
+        unfair.join().unwrap();
+        assert!(unfair.fair_dealing().is_none());
+        assert!(matches!(
+            fair_error(unfair.play(Action::CommitEntropy([0; 32]), 0)),
+            FairDealError::NotEnabled
+        ));
+    }
+
+    #[test]
+    fn test_result_is_published() {
+        let mut room = Room::new(2, 1, 1).unwrap();
+        for _ in 0..2 {
+            room.join().unwrap();
+        }
+        room.play(Action::PredictScore(0), 0).unwrap();
+        room.play(Action::PredictScore(0), 1).unwrap();
+        for _ in 0..2 {
+            assert!(room.result_sender().borrow().is_none());
+            let player = room.current_player().unwrap();
+            let card = room.legal_cards(player).unwrap()[0];
+            room.play(Action::Play(card), player).unwrap();
+        }
+        assert!(room.is_game_over());
+        let result = room.result_sender().borrow().clone().unwrap();
+        assert_eq!(result.rounds.len(), 1);
+        assert_eq!(result.ranking.len(), 2);
+    }
 }
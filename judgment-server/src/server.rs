@@ -7,13 +7,14 @@ use axum::{
     http::request::Parts,
     RequestPartsExt, TypedHeader,
 };
+use judgment::GameConfig;
 use pasetors::{claims::ClaimsValidationRules, keys::AsymmetricKeyPair, version4::V4};
 use serde::Serialize;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::{
-    errors::{InvalidRoomId, InvalidToken, PlayError, RoomJoinError, ServerFull},
+    errors::{CreateRoomError, InvalidRoomId, InvalidToken, PlayError, RoomJoinError, ServerFull},
     room::{Action, Room},
 };
 
@@ -83,12 +84,30 @@ impl Server {
         players: u8,
         starting_hand_size: u8,
         decks: u8,
-    ) -> Result<Uuid, ServerFull> {
+    ) -> Result<Uuid, CreateRoomError> {
+        let config = GameConfig::builder(players, starting_hand_size)
+            .decks(decks)
+            .build()?;
+        self.create_room_with_config(config, false)
+    }
+
+    /// Create a new room in this server for a game played with the rules in
+    /// `config`, where the players take part in shuffling every deal if
+    /// `fair_dealing`, see [`Room::with_fair_dealing`].
+    pub fn create_room_with_config(
+        &mut self,
+        config: GameConfig,
+        fair_dealing: bool,
+    ) -> Result<Uuid, CreateRoomError> {
         if self.max_rooms == self.rooms.len() {
-            return Err(ServerFull);
+            return Err(ServerFull.into());
         }
-        let room = Room::new(players, starting_hand_size, decks);
         let room_id = Uuid::new_v4();
+        let room = if fair_dealing {
+            Room::with_fair_dealing(config)
+        } else {
+            Room::with_config(config)
+        };
         self.rooms.insert(room_id, room);
         Ok(room_id)
     }
@@ -166,7 +185,7 @@ mod tests {
         version4::V4,
     };
 
-    use crate::errors::{RoomJoinError, ServerFull};
+    use crate::errors::{CreateRoomError, RoomJoinError};
 
     use super::Server;
 
@@ -181,7 +200,19 @@ mod tests {
         for _ in 0..3 {
             assert!(matches!(server.create_room(2, 2, 1), Ok(uuid::Uuid { .. })));
         }
-        assert!(matches!(server.create_room(2, 2, 1), Err(ServerFull)));
+        assert!(matches!(
+            server.create_room(2, 2, 1),
+            Err(CreateRoomError::ServerFull(..))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_room_creation() {
+        let mut server = create_server(3);
+        assert!(matches!(
+            server.create_room(4, 14, 1),
+            Err(CreateRoomError::InvalidConfig(..))
+        ));
     }
 
     #[test]
@@ -0,0 +1,277 @@
+use crate::{ClaimRule, InvalidTransition, Judgment, Round, Stage, StateUpdate};
+
+/// A claim for every trick left in a round, waiting for the claimant's
+/// opponents to answer it. See [`Transition::Claim`](crate::Transition::Claim).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Claim {
+    pub player: usize,
+    /// Whether every player agrees to the claim. The claimant and their
+    /// teammates agree from the start.
+    pub accepted: Vec<bool>,
+}
+
+impl Judgment {
+    /// The claim waiting for the opponents of the claimant, if any.
+    pub fn claim(&self) -> Option<&Claim> {
+        match &self.stage {
+            Stage::Claim(_, claim) => Some(claim),
+            _ => None,
+        }
+    }
+
+    /// Whether `player` answers a claim made by `claimant`.
+    fn is_opponent(&self, claimant: usize, player: usize) -> bool {
+        player != claimant
+            && self
+                .config
+                .teams()
+                .is_none_or(|teams| teams.team_of(player) != teams.team_of(claimant))
+    }
+
+    /// Check a claim by `player` during the play of `round`.
+    pub(crate) fn validate_claim(
+        &self,
+        round: &Round,
+        player: usize,
+    ) -> Result<(), InvalidTransition> {
+        if round.player != player {
+            return Err(InvalidTransition::OutOfTurnPlay);
+        }
+        if self.trick.iter().any(Option::is_some) {
+            return Err(InvalidTransition::ClaimDuringTrick);
+        }
+        if self.config.claims() == ClaimRule::Verified {
+            // a single lost trick refutes the claim, which keeps this quick
+            let position = self.double_dummy().expect("the cards are being played");
+            let tricks = self.players[player].hand().len() as u8;
+            if !position.can_take(player, tricks) {
+                return Err(InvalidTransition::UnprovenClaim);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check an answer of `player` to the `claim`; `accept` tells whether
+    /// they agree to it.
+    pub(crate) fn validate_claim_answer(
+        &self,
+        claim: &Claim,
+        player: usize,
+        accept: bool,
+    ) -> Result<(), InvalidTransition> {
+        if player >= claim.accepted.len() || !self.is_opponent(claim.player, player) {
+            return Err(InvalidTransition::NotAnOpponent);
+        }
+        if accept && claim.accepted[player] {
+            return Err(InvalidTransition::ClaimAlreadyAccepted);
+        }
+        Ok(())
+    }
+
+    /// Hold up the play of `round` for the claim by `player`, or settle it at
+    /// once if nobody needs to answer it.
+    pub(crate) fn make_claim(&mut self, round: Round, player: usize) -> Vec<StateUpdate> {
+        let mut updates = vec![StateUpdate::ClaimMade { player }];
+        let claim = Claim {
+            player,
+            accepted: (0..round.trick_scores.len())
+                .map(|seat| !self.is_opponent(player, seat))
+                .collect(),
+        };
+        if claim.accepted.iter().all(|accepted| *accepted)
+            || self.config.claims() == ClaimRule::Verified
+        {
+            updates.extend(self.settle_claim(round, &claim));
+        } else {
+            self.stage = Stage::Claim(round, claim);
+        }
+        updates
+    }
+
+    /// End `round` with the claimant of the accepted `claim` taking every
+    /// trick left.
+    pub(crate) fn settle_claim(&mut self, mut round: Round, claim: &Claim) -> Vec<StateUpdate> {
+        round.trick_scores[claim.player] += self.players[claim.player].hand().len() as u8;
+        for player in &mut self.players {
+            player.assign(std::iter::empty());
+        }
+        let mut updates = vec![StateUpdate::RoundScores(round.trick_scores.clone())];
+        updates.extend(self.end_round(round));
+        updates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use card_deck::standard_deck::{Card, Rank, Suit};
+
+    use crate::{
+        ClaimRule, GameConfig, InvalidTransition, Judgment, Phase, StateUpdate, TeamBid, Teams,
+        Transition,
+    };
+
+    /// A game of `config` where every player predicted zero tricks.
+    fn predicted_game(config: GameConfig) -> Judgment {
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [3; 32] }).unwrap();
+        while let Some(player) = game
+            .current_player()
+            .filter(|_| game.phase() == Phase::Predicting)
+        {
+            let score = game.legal_predictions(player)[0];
+            game.update(Transition::PredictScore { player, score })
+                .unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_claims_need_every_opponent() {
+        let config = GameConfig::builder(3, 3).build().unwrap();
+        let mut game = predicted_game(config);
+        assert_eq!(
+            game.validate(&Transition::Claim { player: 1 }),
+            Err(InvalidTransition::OutOfTurnPlay)
+        );
+        assert_eq!(
+            game.validate(&Transition::AcceptClaim { player: 1 }),
+            Err(InvalidTransition::NoPendingClaim)
+        );
+        let updates = game.update(Transition::Claim { player: 0 }).unwrap();
+        assert_eq!(updates, [StateUpdate::ClaimMade { player: 0 }]);
+        assert_eq!(game.phase(), Phase::Claiming);
+        assert_eq!(game.current_player(), None);
+        assert_eq!(game.claim().unwrap().accepted, [true, false, false]);
+        assert_eq!(
+            game.validate(&Transition::AcceptClaim { player: 0 }),
+            Err(InvalidTransition::NotAnOpponent)
+        );
+        let card = game.hand_of_player(0).unwrap()[0];
+        assert_eq!(
+            game.validate(&Transition::Play { player: 0, card }),
+            Err(InvalidTransition::ClaimPending)
+        );
+
+        // a rejected claim lets the claimant lead
+        game.update(Transition::AcceptClaim { player: 1 }).unwrap();
+        assert_eq!(
+            game.validate(&Transition::AcceptClaim { player: 1 }),
+            Err(InvalidTransition::ClaimAlreadyAccepted)
+        );
+        let updates = game.update(Transition::RejectClaim { player: 2 }).unwrap();
+        assert_eq!(
+            updates,
+            [
+                StateUpdate::ClaimRejected { player: 2 },
+                StateUpdate::TurnChanged(0)
+            ]
+        );
+        assert_eq!(game.claim(), None);
+        game.update(Transition::Play { player: 0, card }).unwrap();
+        assert_eq!(
+            game.validate(&Transition::Claim { player: 1 }),
+            Err(InvalidTransition::ClaimDuringTrick)
+        );
+        while game.trick().iter().any(Option::is_some) {
+            let player = game.current_player().unwrap();
+            let card = game.legal_cards(player)[0];
+            game.update(Transition::Play { player, card }).unwrap();
+        }
+
+        // an accepted claim ends the round
+        let leader = game.current_player().unwrap();
+        let won = game.round_scores().unwrap().to_vec();
+        game.update(Transition::Claim { player: leader }).unwrap();
+        let mut updates = Vec::new();
+        for player in (0..3).filter(|player| *player != leader) {
+            updates = game.update(Transition::AcceptClaim { player }).unwrap();
+        }
+        let mut tricks = won;
+        tricks[leader] += 2;
+        assert_eq!(tricks.iter().sum::<u8>(), 3);
+        assert_eq!(updates[1], StateUpdate::RoundScores(tricks.clone()));
+        assert!(matches!(
+            &updates[2],
+            StateUpdate::RoundEnded { tricks: ended, .. } if *ended == tricks
+        ));
+        assert_eq!(game.phase(), Phase::Dealing);
+        assert!(game.hand_of_player(0).unwrap().is_empty());
+        assert_eq!(game.round_results.last().unwrap().tricks, tricks);
+
+        // and is undone like any other move
+        game.undo().unwrap();
+        assert_eq!(game.phase(), Phase::Claiming);
+        assert_eq!(game.hand_of_player(leader).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_teammates_do_not_answer_claims() {
+        let config = GameConfig::builder(4, 2)
+            .teams(Teams::new(vec![0, 1, 0, 1], TeamBid::Sum))
+            .build()
+            .unwrap();
+        let mut game = predicted_game(config);
+        game.update(Transition::Claim { player: 0 }).unwrap();
+        assert_eq!(game.claim().unwrap().accepted, [true, false, true, false]);
+        assert_eq!(
+            game.validate(&Transition::RejectClaim { player: 2 }),
+            Err(InvalidTransition::NotAnOpponent)
+        );
+        game.update(Transition::AcceptClaim { player: 1 }).unwrap();
+        game.update(Transition::AcceptClaim { player: 3 }).unwrap();
+        assert_eq!(game.round_results[0].tricks, [2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_verified_claims() {
+        let card = |suit, rank| Card::new_normal(suit, rank);
+        let config = GameConfig::builder(2, 2)
+            .claims(ClaimRule::Verified)
+            .build()
+            .unwrap();
+        // spades are trump, and player 0 leads
+        let mut game = predicted_game(config.clone());
+        game.players[0].assign(
+            [
+                card(Suit::Spades, Rank::Ace),
+                card(Suit::Hearts, Rank::Numeric(2)),
+            ]
+            .into_iter(),
+        );
+        game.players[1].assign(
+            [
+                card(Suit::Spades, Rank::King),
+                card(Suit::Hearts, Rank::Numeric(3)),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            game.validate(&Transition::Claim { player: 0 }),
+            Err(InvalidTransition::UnprovenClaim)
+        );
+        assert_eq!(game.phase(), Phase::Playing);
+
+        let mut game = predicted_game(config);
+        game.players[0].assign(
+            [
+                card(Suit::Spades, Rank::Ace),
+                card(Suit::Spades, Rank::King),
+            ]
+            .into_iter(),
+        );
+        game.players[1].assign(
+            [
+                card(Suit::Hearts, Rank::Ace),
+                card(Suit::Hearts, Rank::King),
+            ]
+            .into_iter(),
+        );
+        let updates = game.update(Transition::Claim { player: 0 }).unwrap();
+        assert!(updates
+            .iter()
+            .any(|update| matches!(update, StateUpdate::RoundEnded { .. })));
+        assert_eq!(game.round_results[0].tricks, [2, 0]);
+    }
+}
@@ -0,0 +1,683 @@
+use std::fmt;
+
+use card_deck::standard_deck::Suit;
+
+use crate::{GameConfigError, Scoring, TeamBid, Teams};
+
+/// The rules a game of Judgment is played with.
+///
+/// Use [`GameConfig::builder`] to create one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameConfig {
+    players: u8,
+    max_hand_size: u8,
+    decks: u8,
+    jokers: u8,
+    hand_sizes: HandSizeSchedule,
+    trump_rotation: Vec<Option<Suit>>,
+    trump_selection: TrumpSelection,
+    last_player_restriction: bool,
+    scoring: Scoring,
+    duplicate_cards: DuplicateCardRule,
+    bidding: BiddingMode,
+    blind_multiplier: Option<u8>,
+    teams: Option<Teams>,
+    claims: ClaimRule,
+}
+
+impl GameConfig {
+    /// Start building the rules for a game of `players` where no round deals
+    /// more than `max_hand_size` cards per player.
+    ///
+    /// Every other rule starts out as the classic game: there are no jokers,
+    /// hand sizes descend by one card per round, trump rotates through
+    /// Spades, Hearts, Clubs, Diamonds and no trump, the last player to
+    /// predict cannot make the predictions add up to the hand size,
+    /// [`Scoring::Standard`] is used, of two identical cards the first one
+    /// played wins, predictions are made openly, one player after the other,
+    /// with no blind predictions, everyone plays for themselves and a claim
+    /// needs the agreement of every opponent.
+    pub fn builder(players: u8, max_hand_size: u8) -> GameConfigBuilder {
+        GameConfigBuilder {
+            players,
+            max_hand_size,
+            decks: None,
+            jokers: 0,
+            hand_sizes: HandSizeSchedule::Descending,
+            trump_rotation: vec![
+                Some(Suit::Spades),
+                Some(Suit::Hearts),
+                Some(Suit::Clubs),
+                Some(Suit::Diamonds),
+                None,
+            ],
+            trump_selection: TrumpSelection::Rotation,
+            last_player_restriction: true,
+            scoring: Scoring::Standard,
+            duplicate_cards: DuplicateCardRule::FirstWins,
+            bidding: BiddingMode::Sequential,
+            blind_multiplier: None,
+            teams: None,
+            claims: ClaimRule::Agreement,
+        }
+    }
+
+    pub fn players(&self) -> u8 {
+        self.players
+    }
+
+    pub fn max_hand_size(&self) -> u8 {
+        self.max_hand_size
+    }
+
+    pub fn decks(&self) -> u8 {
+        self.decks
+    }
+
+    /// The number of jokers in every deck.
+    pub fn jokers(&self) -> u8 {
+        self.jokers
+    }
+
+    pub fn hand_size_schedule(&self) -> HandSizeSchedule {
+        self.hand_sizes
+    }
+
+    pub fn trump_rotation(&self) -> &[Option<Suit>] {
+        &self.trump_rotation
+    }
+
+    pub fn trump_selection(&self) -> TrumpSelection {
+        self.trump_selection
+    }
+
+    pub fn last_player_restriction(&self) -> bool {
+        self.last_player_restriction
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    pub fn duplicate_cards(&self) -> DuplicateCardRule {
+        self.duplicate_cards
+    }
+
+    pub fn bidding(&self) -> BiddingMode {
+        self.bidding
+    }
+
+    /// What the points of a blind prediction are multiplied by, or `None` if
+    /// blind predictions are not allowed.
+    pub fn blind_multiplier(&self) -> Option<u8> {
+        self.blind_multiplier
+    }
+
+    /// The teams the players are split into, if they play in teams.
+    pub fn teams(&self) -> Option<&Teams> {
+        self.teams.as_ref()
+    }
+
+    /// How a claim for the remaining tricks of a round is settled.
+    pub fn claims(&self) -> ClaimRule {
+        self.claims
+    }
+
+    /// Number of cards dealt to each player in every round, in order.
+    pub fn hand_sizes(&self) -> Vec<u8> {
+        self.hand_sizes.hand_sizes(self.max_hand_size)
+    }
+
+    /// The number of rounds in the game.
+    pub fn rounds(&self) -> usize {
+        self.hand_sizes().len()
+    }
+
+    /// The trump suit of the `round`th round, counting from 0, as far as it
+    /// is known before the cards are dealt. With [`TrumpSelection::TurnUp`]
+    /// that is never the case, and this is always `None`.
+    pub fn trump_for_round(&self, round: usize) -> Option<Suit> {
+        match self.trump_selection {
+            TrumpSelection::Rotation => self.trump_rotation[round % self.trump_rotation.len()],
+            TrumpSelection::TurnUp => None,
+        }
+    }
+}
+
+/// Builder for [`GameConfig`].
+#[derive(Debug, Clone)]
+pub struct GameConfigBuilder {
+    players: u8,
+    max_hand_size: u8,
+    decks: Option<u8>,
+    jokers: u8,
+    hand_sizes: HandSizeSchedule,
+    trump_rotation: Vec<Option<Suit>>,
+    trump_selection: TrumpSelection,
+    last_player_restriction: bool,
+    scoring: Scoring,
+    duplicate_cards: DuplicateCardRule,
+    bidding: BiddingMode,
+    blind_multiplier: Option<u8>,
+    teams: Option<Teams>,
+    claims: ClaimRule,
+}
+
+impl GameConfigBuilder {
+    /// Use exactly this many card decks. By default, the fewest decks that can
+    /// deal the largest round are used.
+    pub fn decks(mut self, decks: u8) -> Self {
+        self.decks = Some(decks);
+        self
+    }
+
+    /// Add this many jokers, at most 2, to every deck. A joker beats every
+    /// other card, trumps included, and may be played at any time; it has no
+    /// suit, so when a joker is led the first card that is not one decides
+    /// the suit to follow. Two jokers in a trick are identical cards, settled
+    /// by [`GameConfigBuilder::duplicate_cards`].
+    pub fn jokers(mut self, jokers: u8) -> Self {
+        self.jokers = jokers;
+        self
+    }
+
+    /// How the hand size changes from round to round.
+    pub fn hand_sizes(mut self, schedule: HandSizeSchedule) -> Self {
+        self.hand_sizes = schedule;
+        self
+    }
+
+    /// The trump suit of each round, repeated cyclically; `None` is a round
+    /// without trump.
+    pub fn trump_rotation(mut self, rotation: Vec<Option<Suit>>) -> Self {
+        self.trump_rotation = rotation;
+        self
+    }
+
+    /// How the trump suit of every round is chosen.
+    pub fn trump_selection(mut self, selection: TrumpSelection) -> Self {
+        self.trump_selection = selection;
+        self
+    }
+
+    /// Whether the last player to predict is forbidden from making the sum of
+    /// all predictions equal the number of tricks in the round. Never applies
+    /// to [`BiddingMode::Sealed`], where nobody knows the other predictions.
+    pub fn last_player_restriction(mut self, enabled: bool) -> Self {
+        self.last_player_restriction = enabled;
+        self
+    }
+
+    /// How a round's predictions and tricks turn into points.
+    pub fn scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Which of two identical cards in a trick wins, which can only happen
+    /// when playing with more than one deck.
+    pub fn duplicate_cards(mut self, rule: DuplicateCardRule) -> Self {
+        self.duplicate_cards = rule;
+        self
+    }
+
+    /// How the players make their predictions.
+    pub fn bidding(mut self, mode: BiddingMode) -> Self {
+        self.bidding = mode;
+        self
+    }
+
+    /// Allow players to predict before the cards of a round are dealt. Points
+    /// gained or lost on a blind prediction are multiplied by `multiplier`.
+    pub fn blind_predictions(mut self, multiplier: u8) -> Self {
+        self.blind_multiplier = Some(multiplier);
+        self
+    }
+
+    /// Play in teams. Besides every player's own score, every team scores
+    /// its prediction against the tricks won by its members. With
+    /// [`TeamBid::Joint`], the last player restriction applies to the last
+    /// team to predict. A team's points are only multiplied for a blind
+    /// prediction if all its members predicted blind.
+    pub fn teams(mut self, teams: Teams) -> Self {
+        self.teams = Some(teams);
+        self
+    }
+
+    /// How a claim for the remaining tricks of a round is settled, see
+    /// [`Transition::Claim`](crate::Transition::Claim).
+    pub fn claims(mut self, rule: ClaimRule) -> Self {
+        self.claims = rule;
+        self
+    }
+
+    /// Finish building the config.
+    ///
+    /// # Errors
+    /// Errors if the rules do not describe a playable game, see
+    /// [`GameConfigError`] for the details.
+    pub fn build(self) -> Result<GameConfig, GameConfigError> {
+        if self.players == 0 {
+            return Err(GameConfigError::ZeroPlayers);
+        }
+        if self.max_hand_size == 0 || self.max_hand_size > 13 {
+            return Err(GameConfigError::HandSizeOutOfRange {
+                hand_size: self.max_hand_size,
+            });
+        }
+        if u16::from(self.max_hand_size) * u16::from(self.players) > u16::from(u8::MAX) {
+            return Err(GameConfigError::TooManyPlayers {
+                players: self.players,
+                max: u8::MAX / self.max_hand_size,
+            });
+        }
+        if self.trump_rotation.is_empty() {
+            return Err(GameConfigError::EmptyTrumpRotation);
+        }
+        if self.jokers > 2 {
+            return Err(GameConfigError::TooManyJokers {
+                jokers: self.jokers,
+            });
+        }
+        if let Some(teams) = &self.teams {
+            if teams.seats().len() != usize::from(self.players) {
+                return Err(GameConfigError::TeamSeatsMismatch {
+                    seats: teams.seats().len(),
+                    players: self.players,
+                });
+            }
+            if let Some(&team) = teams
+                .seats()
+                .iter()
+                .find(|team| **team >= usize::from(self.players))
+            {
+                return Err(GameConfigError::TeamOutOfRange {
+                    team,
+                    players: self.players,
+                });
+            }
+            if teams.count() < 2 {
+                return Err(GameConfigError::TooFewTeams {
+                    teams: teams.count(),
+                });
+            }
+            if let Some(team) =
+                (0..teams.count()).find(|team| teams.members(*team).next().is_none())
+            {
+                return Err(GameConfigError::EmptyTeam { team });
+            }
+        }
+        // SAFETY
+        // cannot overflow because of the player count check above
+        let cards = self.max_hand_size * self.players;
+        let required_decks = cards.div_ceil(52 + self.jokers);
+        let decks = if let Some(decks) = self.decks {
+            if decks < required_decks {
+                return Err(GameConfigError::TooFewDecks {
+                    decks,
+                    required: required_decks,
+                });
+            }
+            decks
+        } else {
+            required_decks
+        };
+        Ok(GameConfig {
+            players: self.players,
+            max_hand_size: self.max_hand_size,
+            decks,
+            jokers: self.jokers,
+            hand_sizes: self.hand_sizes,
+            trump_rotation: self.trump_rotation,
+            trump_selection: self.trump_selection,
+            last_player_restriction: self.last_player_restriction
+                && self.bidding == BiddingMode::Sequential,
+            scoring: self.scoring,
+            duplicate_cards: self.duplicate_cards,
+            bidding: self.bidding,
+            blind_multiplier: self.blind_multiplier,
+            teams: self.teams,
+            claims: self.claims,
+        })
+    }
+}
+
+/// How the number of cards dealt to each player changes from round to round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandSizeSchedule {
+    /// From the maximum hand size down to one card.
+    #[default]
+    Descending,
+    /// From one card up to the maximum hand size.
+    Ascending,
+    /// From the maximum hand size down to one card and back up again.
+    UpAndDown,
+}
+
+impl HandSizeSchedule {
+    /// Hand sizes of every round, in order, when no round may deal more than
+    /// `max_hand_size` cards.
+    pub fn hand_sizes(&self, max_hand_size: u8) -> Vec<u8> {
+        match self {
+            HandSizeSchedule::Descending => (1..=max_hand_size).rev().collect(),
+            HandSizeSchedule::Ascending => (1..=max_hand_size).collect(),
+            HandSizeSchedule::UpAndDown => {
+                (1..=max_hand_size).rev().chain(2..=max_hand_size).collect()
+            }
+        }
+    }
+}
+
+/// How the trump suit of a round is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrumpSelection {
+    /// Follow the trump rotation of the config.
+    #[default]
+    Rotation,
+    /// After the deal, the next card of the deck is turned up and its suit is
+    /// trump. There is no trump if every card was dealt or the card is a
+    /// joker.
+    TurnUp,
+}
+
+/// What happens when identical cards from different decks land in the same
+/// trick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicateCardRule {
+    /// The copy played first beats the later ones.
+    #[default]
+    FirstWins,
+    /// The copy played last beats the earlier ones.
+    LastWins,
+    /// Identical cards cancel each other and none of them can win. The trick
+    /// goes to the best card left, and to the leader if no trump and no card
+    /// of the suit led is left.
+    Cancel,
+}
+
+/// How the players make their predictions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BiddingMode {
+    /// Predictions are made openly, one player after the other, starting
+    /// left of the dealer.
+    #[default]
+    Sequential,
+    /// Every player commits to a prediction without seeing the others, in
+    /// any order. The predictions are revealed together once all are in.
+    Sealed,
+}
+
+/// How a claim for the remaining tricks of a round is settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClaimRule {
+    /// Every opponent of the claimant has to accept the claim.
+    #[default]
+    Agreement,
+    /// The engine rejects a claim unless the claimant takes every remaining
+    /// trick however the others play, as found by
+    /// [`DoubleDummy::can_take`](crate::DoubleDummy::can_take), and
+    /// settles it at once otherwise.
+    Verified,
+}
+
+impl fmt::Display for DuplicateCardRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuplicateCardRule::FirstWins => write!(f, "the first of identical cards wins"),
+            DuplicateCardRule::LastWins => write!(f, "the last of identical cards wins"),
+            DuplicateCardRule::Cancel => write!(f, "identical cards cancel each other"),
+        }
+    }
+}
+
+impl fmt::Display for GameConfig {
+    /// A summary of the rules, one per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suit_name = |suit: &Option<Suit>| match suit {
+            Some(Suit::Clubs) => "clubs",
+            Some(Suit::Diamonds) => "diamonds",
+            Some(Suit::Hearts) => "hearts",
+            Some(Suit::Spades) => "spades",
+            None => "no trump",
+        };
+        write!(
+            f,
+            "{} players, {} {}",
+            self.players,
+            self.decks,
+            if self.decks == 1 { "deck" } else { "decks" }
+        )?;
+        match self.jokers {
+            0 => writeln!(f)?,
+            1 => writeln!(f, " with a joker each")?,
+            jokers => writeln!(f, " with {jokers} jokers each")?,
+        }
+        let schedule = match self.hand_sizes {
+            HandSizeSchedule::Descending => "down",
+            HandSizeSchedule::Ascending => "up",
+            HandSizeSchedule::UpAndDown => "down and back up",
+        };
+        writeln!(
+            f,
+            "hand sizes: {schedule}, at most {} cards",
+            self.max_hand_size
+        )?;
+        match self.trump_selection {
+            TrumpSelection::Rotation => {
+                let rotation: Vec<&str> = self.trump_rotation.iter().map(suit_name).collect();
+                writeln!(f, "trump: {}", rotation.join(", "))?;
+            }
+            TrumpSelection::TurnUp => writeln!(f, "trump: turned up after the deal")?,
+        }
+        if self.last_player_restriction {
+            writeln!(
+                f,
+                "the last prediction cannot make the predictions add up to the hand size"
+            )?;
+        }
+        if self.bidding == BiddingMode::Sealed {
+            writeln!(f, "predictions: sealed, revealed together")?;
+        }
+        if let Some(multiplier) = self.blind_multiplier {
+            writeln!(f, "blind predictions score {multiplier} times the points")?;
+        }
+        if let Some(teams) = &self.teams {
+            let bid = match teams.bid() {
+                TeamBid::Sum => "predictions added up",
+                TeamBid::Joint => "one joint prediction each",
+            };
+            writeln!(f, "teams: {teams}, {bid}")?;
+        }
+        if self.claims == ClaimRule::Verified {
+            writeln!(f, "claims: checked by the engine")?;
+        }
+        match &self.scoring {
+            Scoring::Standard => writeln!(f, "scoring: exact or lose")?,
+            Scoring::TenPlusBid => writeln!(f, "scoring: ten plus bid")?,
+            Scoring::PenaltyPerTrick { hit_bonus, penalty } => writeln!(
+                f,
+                "scoring: {hit_bonus} plus bid, {penalty} off per trick missed"
+            )?,
+            Scoring::ZeroBidBonus { bonus } => {
+                writeln!(f, "scoring: exact or lose, {bonus} for a zero bid")?
+            }
+            Scoring::Custom(_) => writeln!(f, "scoring: custom")?,
+        }
+        write!(f, "{}", self.duplicate_cards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use card_deck::standard_deck::Suit;
+
+    use crate::{GameConfigError, TeamBid, Teams};
+
+    use super::{BiddingMode, DuplicateCardRule, GameConfig, HandSizeSchedule, TrumpSelection};
+
+    #[test]
+    fn test_hand_size_schedules() {
+        assert_eq!(HandSizeSchedule::Descending.hand_sizes(3), [3, 2, 1]);
+        assert_eq!(HandSizeSchedule::Ascending.hand_sizes(3), [1, 2, 3]);
+        assert_eq!(HandSizeSchedule::UpAndDown.hand_sizes(3), [3, 2, 1, 2, 3]);
+        assert_eq!(HandSizeSchedule::UpAndDown.hand_sizes(1), [1]);
+    }
+
+    #[test]
+    fn test_deck_estimation() {
+        assert_eq!(GameConfig::builder(4, 13).build().unwrap().decks(), 1);
+        assert_eq!(GameConfig::builder(5, 13).build().unwrap().decks(), 2);
+        assert_eq!(
+            GameConfig::builder(5, 13).decks(3).build().unwrap().decks(),
+            3
+        );
+        assert_eq!(GameConfig::builder(255, 1).build().unwrap().decks(), 5);
+        // 54 cards fit in one deck only with both jokers
+        let config = GameConfig::builder(6, 9).jokers(2).build().unwrap();
+        assert_eq!(config.decks(), 1);
+        let config = GameConfig::builder(6, 9).jokers(1).build().unwrap();
+        assert_eq!(config.decks(), 2);
+        assert!(config
+            .to_string()
+            .starts_with("6 players, 2 decks with a joker each\n"));
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        assert_eq!(
+            GameConfig::builder(0, 5).build(),
+            Err(GameConfigError::ZeroPlayers)
+        );
+        assert_eq!(
+            GameConfig::builder(5, 13).decks(1).build(),
+            Err(GameConfigError::TooFewDecks {
+                decks: 1,
+                required: 2
+            })
+        );
+        assert_eq!(
+            GameConfig::builder(4, 0).build(),
+            Err(GameConfigError::HandSizeOutOfRange { hand_size: 0 })
+        );
+        assert_eq!(
+            GameConfig::builder(4, 14).build(),
+            Err(GameConfigError::HandSizeOutOfRange { hand_size: 14 })
+        );
+        assert_eq!(
+            GameConfig::builder(20, 13).build(),
+            Err(GameConfigError::TooManyPlayers {
+                players: 20,
+                max: 19
+            })
+        );
+        assert!(GameConfig::builder(19, 13).build().is_ok());
+        assert_eq!(
+            GameConfig::builder(4, 5).trump_rotation(Vec::new()).build(),
+            Err(GameConfigError::EmptyTrumpRotation)
+        );
+        assert_eq!(
+            GameConfig::builder(4, 5).jokers(3).build(),
+            Err(GameConfigError::TooManyJokers { jokers: 3 })
+        );
+        assert_eq!(
+            GameConfig::builder(4, 5)
+                .teams(Teams::new(vec![0, 1, 0], TeamBid::Sum))
+                .build(),
+            Err(GameConfigError::TeamSeatsMismatch {
+                seats: 3,
+                players: 4
+            })
+        );
+        assert_eq!(
+            GameConfig::builder(4, 5)
+                .teams(Teams::new(vec![0, 2, 0, 2], TeamBid::Sum))
+                .build(),
+            Err(GameConfigError::EmptyTeam { team: 1 })
+        );
+        assert_eq!(
+            GameConfig::builder(4, 5)
+                .teams(Teams::new(vec![0, usize::MAX, 0, 1], TeamBid::Sum))
+                .build(),
+            Err(GameConfigError::TeamOutOfRange {
+                team: usize::MAX,
+                players: 4
+            })
+        );
+        assert_eq!(
+            GameConfig::builder(4, 5)
+                .teams(Teams::new(vec![0; 4], TeamBid::Sum))
+                .build(),
+            Err(GameConfigError::TooFewTeams { teams: 1 })
+        );
+    }
+
+    #[test]
+    fn test_trump_rotation_wraps() {
+        let config = GameConfig::builder(4, 5)
+            .trump_rotation(vec![Some(Suit::Hearts), None])
+            .build()
+            .unwrap();
+        assert_eq!(config.trump_for_round(0), Some(Suit::Hearts));
+        assert_eq!(config.trump_for_round(1), None);
+        assert_eq!(config.trump_for_round(4), Some(Suit::Hearts));
+        let config = GameConfig::builder(4, 5)
+            .trump_selection(TrumpSelection::TurnUp)
+            .build()
+            .unwrap();
+        assert_eq!(config.trump_for_round(0), None);
+        assert!(config
+            .to_string()
+            .contains("trump: turned up after the deal"));
+    }
+
+    #[test]
+    fn test_rules_description() {
+        let config = GameConfig::builder(5, 13)
+            .trump_rotation(vec![Some(Suit::Hearts), None])
+            .duplicate_cards(DuplicateCardRule::Cancel)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.to_string(),
+            "5 players, 2 decks\n\
+             hand sizes: down, at most 13 cards\n\
+             trump: hearts, no trump\n\
+             the last prediction cannot make the predictions add up to the hand size\n\
+             scoring: exact or lose\n\
+             identical cards cancel each other"
+        );
+        let config = GameConfig::builder(2, 3)
+            .last_player_restriction(false)
+            .build()
+            .unwrap();
+        let description = config.to_string();
+        assert!(!description.contains("last prediction"));
+        assert!(description.ends_with("the first of identical cards wins"));
+        // nobody can be the last to predict when predictions are sealed
+        let config = GameConfig::builder(4, 5)
+            .bidding(BiddingMode::Sealed)
+            .blind_predictions(2)
+            .build()
+            .unwrap();
+        assert!(!config.last_player_restriction());
+        let description = config.to_string();
+        assert!(!description.contains("last prediction"));
+        assert!(description.contains(
+            "predictions: sealed, revealed together\n\
+             blind predictions score 2 times the points\n"
+        ));
+        let config = GameConfig::builder(4, 5)
+            .teams(Teams::new(vec![0, 1, 0, 1], TeamBid::Joint))
+            .build()
+            .unwrap();
+        assert!(config
+            .to_string()
+            .contains("teams: 0 & 2 vs 1 & 3, one joint prediction each\n"));
+    }
+}
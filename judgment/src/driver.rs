@@ -0,0 +1,127 @@
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::{Judgment, Phase, StateUpdate, Transition};
+
+/// Plays random legal moves for every player of a game.
+///
+/// The moves are picked by a seeded RNG, so the same seed on the same game
+/// always plays the same moves. This makes it useful for tests and
+/// simulations that need whole games without caring about strategy.
+///
+/// # Examples
+/// ```
+/// use judgment::{Judgment, RandomDriver};
+///
+/// let mut game = Judgment::new(4, 5, None);
+/// game.start().unwrap();
+/// RandomDriver::new(42).play_out(&mut game);
+/// assert!(game.is_over());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomDriver {
+    rng: ChaCha20Rng,
+}
+
+impl RandomDriver {
+    pub fn new(seed: u64) -> Self {
+        RandomDriver {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// A random transition that the game accepts in its current state, or
+    /// `None` if the game is not in progress.
+    pub fn next_transition(&mut self, game: &Judgment) -> Option<Transition> {
+        match game.phase() {
+            Phase::NotStarted | Phase::Over => None,
+            Phase::Dealing => {
+                // now and then, somebody predicts blind before the deal
+                if game.config().blind_multiplier().is_some() && self.rng.gen_bool(0.2) {
+                    let player = self.rng.gen_range(0..usize::from(game.config().players()));
+                    let legal = game.legal_blind_predictions(player);
+                    if let Some(score) = legal.choose(&mut self.rng) {
+                        return Some(Transition::PredictBlind {
+                            player,
+                            score: *score,
+                        });
+                    }
+                }
+                Some(Transition::Deal {
+                    seed: self.rng.gen(),
+                })
+            }
+            Phase::Predicting => {
+                let player = game.current_player()?;
+                let score = *game.legal_predictions(player).choose(&mut self.rng)?;
+                Some(Transition::PredictScore { player, score })
+            }
+            Phase::Playing => {
+                let player = game.current_player()?;
+                let card = *game.legal_cards(player).choose(&mut self.rng)?;
+                Some(Transition::Play { player, card })
+            }
+            // the driver never claims, but answers the claims of others
+            Phase::Claiming => {
+                let waiting: Vec<usize> = game
+                    .claim()?
+                    .accepted
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, accepted)| !**accepted)
+                    .map(|(player, _)| player)
+                    .collect();
+                let player = *waiting.choose(&mut self.rng)?;
+                Some(if self.rng.gen_bool(0.5) {
+                    Transition::AcceptClaim { player }
+                } else {
+                    Transition::RejectClaim { player }
+                })
+            }
+        }
+    }
+
+    /// Apply random transitions until the game is no longer in progress and
+    /// return all the updates they caused, in order.
+    pub fn play_out(&mut self, game: &mut Judgment) -> Vec<StateUpdate> {
+        let mut updates = Vec::new();
+        while let Some(transition) = self.next_transition(game) {
+            updates.extend(
+                game.update(transition)
+                    .expect("the driver only picks legal transitions"),
+            );
+        }
+        updates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Judgment;
+
+    use super::RandomDriver;
+
+    #[test]
+    fn test_same_seed_plays_same_game() {
+        let play = |seed| {
+            let mut game = Judgment::new(5, 6, None);
+            game.start().unwrap();
+            RandomDriver::new(seed).play_out(&mut game);
+            game.history().to_vec()
+        };
+        assert_eq!(play(1), play(1));
+        assert_ne!(play(1), play(2));
+    }
+
+    #[test]
+    fn test_nothing_to_drive_outside_of_a_game() {
+        let mut game = Judgment::new(3, 2, None);
+        let mut driver = RandomDriver::new(0);
+        assert!(driver.next_transition(&game).is_none());
+        assert!(driver.play_out(&mut game).is_empty());
+        game.start().unwrap();
+        driver.play_out(&mut game);
+        assert!(game.is_over());
+        assert!(driver.next_transition(&game).is_none());
+    }
+}
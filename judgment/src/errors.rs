@@ -1,4 +1,4 @@
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InvalidTransition {
     #[error("cannot deal cards before the game has started")]
@@ -25,4 +25,149 @@ pub enum InvalidTransition {
     NoSuchPlayerCard,
     #[error("must match the suit of the first card when possible")]
     SuitMismatch,
+    #[error("blind predictions are not allowed in this game")]
+    BlindPredictionNotAllowed,
+    #[error("blind predictions must be made before the cards are dealt")]
+    BlindPredictionAfterDeal,
+    #[error("a claim must be made before leading a trick")]
+    ClaimDuringTrick,
+    #[error("the claimant is not sure to take every trick left")]
+    UnprovenClaim,
+    #[error("cannot play while a claim waits for an answer")]
+    ClaimPending,
+    #[error("there is no claim to answer")]
+    NoPendingClaim,
+    #[error("only the claimant's opponents answer a claim")]
+    NotAnOpponent,
+    #[error("the claim has already been accepted by this player")]
+    ClaimAlreadyAccepted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InvalidRewind {
+    #[error("there is no transition to undo")]
+    NothingToUndo,
+    #[error(
+        "cannot rewind to transition {requested}, only {available} transitions have been made"
+    )]
+    BeyondHistory { requested: usize, available: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameConfigError {
+    #[error("a game needs at least one player")]
+    ZeroPlayers,
+    #[error("{decks} decks are not enough for the given number of players and hand size, at least {required} are needed")]
+    TooFewDecks { decks: u8, required: u8 },
+    #[error("hand size must be between 1 and 13, got {hand_size}")]
+    HandSizeOutOfRange { hand_size: u8 },
+    #[error("at most {max} players can play with the given hand size, got {players}")]
+    TooManyPlayers { players: u8, max: u8 },
+    #[error("trump rotation needs at least one entry")]
+    EmptyTrumpRotation,
+    #[error("a deck has at most 2 jokers, got {jokers}")]
+    TooManyJokers { jokers: u8 },
+    #[error("the teams have {seats} seats, but there are {players} players")]
+    TeamSeatsMismatch { seats: usize, players: u8 },
+    #[error("team {team} has no players, teams must be numbered from 0 without gaps")]
+    EmptyTeam { team: usize },
+    #[error("there are only {players} players, so there cannot be a team {team}")]
+    TeamOutOfRange { team: usize, players: u8 },
+    #[error("playing in teams needs at least two teams, got {teams}")]
+    TooFewTeams { teams: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InvalidPosition {
+    #[error("the trick needs exactly one slot per hand")]
+    PlayerCountMismatch,
+    #[error("the leader is not one of the players")]
+    LeaderOutOfRange,
+    #[error("the cards of a trick are played in turn starting from the leader")]
+    TrickOutOfOrder,
+    #[error("every player must hold a card for each trick left to play")]
+    UnevenHands,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("the game cannot be saved: {0}")]
+    Unserializable(String),
+    #[error("the snapshot is malformed: {0}")]
+    Malformed(String),
+    #[error("the snapshot has no schema version")]
+    MissingVersion,
+    #[error("snapshot schema version {version} is not supported, this version of the crate reads versions 1 to {supported}")]
+    UnsupportedVersion { version: u64, supported: u32 },
+}
+
+/// A problem found while reading a [`GameRecord`](crate::GameRecord), at a
+/// line and column counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[error("line {line}, column {column}: {kind}")]
+pub struct NotationError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: NotationErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotationErrorKind {
+    #[error("expected {expected}, found `{found}`")]
+    Unexpected { expected: String, found: String },
+    #[error("unknown tag `{0}`")]
+    UnknownTag(String),
+    #[error("tag `{0}` appears more than once")]
+    DuplicateTag(String),
+    #[error("the header has no `{0}` tag")]
+    MissingTag(String),
+    #[error("`{value}` is not a valid value for tag `{tag}`")]
+    InvalidValue { tag: String, value: String },
+    #[error("the header does not describe a playable game: {0}")]
+    InvalidConfig(GameConfigError),
+    #[error("expected round {expected}, found round {found}")]
+    RoundOutOfOrder { expected: usize, found: usize },
+    #[error("there are more deals than seeds in the header")]
+    MissingSeed,
+    #[error("no deal uses this seed")]
+    UnusedSeed,
+    #[error("`{0}` is not a card")]
+    UnknownCard(String),
+    #[error("a trick must be complete before the next one starts")]
+    IncompleteTrick,
+    #[error("illegal move: {0}")]
+    IllegalTransition(InvalidTransition),
+}
+
+impl NotationErrorKind {
+    pub(crate) fn unexpected(expected: &str, found: &str) -> Self {
+        NotationErrorKind::Unexpected {
+            expected: expected.to_string(),
+            found: found.to_string(),
+        }
+    }
+}
+
+/// Why a deal could not be verified, see
+/// [`Judgment::verify_deals`](crate::Judgment::verify_deals). Deals are
+/// counted from 0.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnfairDeal {
+    #[error("the game has {deals} deals, but there are {proofs} proofs")]
+    DealCount { deals: usize, proofs: usize },
+    #[error("the dealer's seed of deal {deal} does not match its commitment")]
+    DealerCommitment { deal: usize },
+    #[error("not every player's entropy of deal {deal} was revealed")]
+    MissingEntropy { deal: usize },
+    #[error("the entropy of player {player} in deal {deal} does not match their commitment")]
+    PlayerCommitment { deal: usize, player: usize },
+    #[error("deal {deal} was not shuffled with the seed of the revealed values")]
+    SeedMismatch { deal: usize },
 }
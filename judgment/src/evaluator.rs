@@ -0,0 +1,391 @@
+use card_deck::standard_deck::{Card, Rank, StandardDeckBuilder, Suit};
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::{player::Player, trick_winner, DuplicateCardRule, Judgment, Stage};
+
+/// Estimates how many tricks a hand wins, to help with predictions.
+///
+/// The cards the player cannot see are dealt to the other players at random
+/// a number of times, and each of those deals is played out with every
+/// player trying to win as many tricks as possible: the leader plays their
+/// strongest card, and the others win as cheaply as they can or throw away
+/// their weakest card. How often the hand won each number of tricks is its
+/// [`TrickDistribution`].
+///
+/// # Examples
+/// ```
+/// use card_deck::standard_deck::{Card, Rank, Suit};
+/// use judgment::HandEvaluator;
+///
+/// let hand = [
+///     Card::new_normal(Suit::Spades, Rank::Ace),
+///     Card::new_normal(Suit::Spades, Rank::King),
+///     Card::new_normal(Suit::Hearts, Rank::Numeric(2)),
+/// ];
+/// let mut evaluator = HandEvaluator::new(200, 7);
+/// let distribution = evaluator.evaluate(&hand, Some(&Suit::Spades), 4, 1, 1);
+/// // the two top trumps always win
+/// assert_eq!(distribution.probability(0) + distribution.probability(1), 0.0);
+/// assert!(distribution.expected() >= 2.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HandEvaluator {
+    samples: usize,
+    jokers: u8,
+    duplicates: DuplicateCardRule,
+    rng: ChaCha20Rng,
+}
+
+impl HandEvaluator {
+    /// An evaluator playing out `samples` random deals per hand, dealing
+    /// them with an RNG seeded by `seed`.
+    pub fn new(samples: usize, seed: u64) -> Self {
+        HandEvaluator {
+            samples,
+            jokers: 0,
+            duplicates: DuplicateCardRule::FirstWins,
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Deal from decks with this many jokers each. There are none by
+    /// default.
+    pub fn jokers(mut self, jokers: u8) -> Self {
+        self.jokers = jokers;
+        self
+    }
+
+    /// Settle identical cards in a trick by `rule`. The first one played
+    /// wins by default.
+    pub fn duplicate_cards(mut self, rule: DuplicateCardRule) -> Self {
+        self.duplicates = rule;
+        self
+    }
+
+    /// The distribution of the tricks won by `hand` in a round of `players`
+    /// dealt from `decks` decks, when `trump_suit` is trump and the player
+    /// sits `seat` places after the leader of the first trick.
+    ///
+    /// An empty hand wins no tricks, which is certain.
+    ///
+    /// # Panics
+    /// Panics if `seat` is not one of the `players`, or if the decks do not
+    /// hold enough cards to give everyone as many cards as `hand`.
+    pub fn evaluate(
+        &mut self,
+        hand: &[Card],
+        trump_suit: Option<&Suit>,
+        players: u8,
+        seat: usize,
+        decks: u8,
+    ) -> TrickDistribution {
+        let players = usize::from(players);
+        assert!(seat < players, "the seat must be one of the players");
+        if hand.is_empty() {
+            return TrickDistribution {
+                probabilities: vec![1.0],
+            };
+        }
+        let deck_size = (52 + usize::from(self.jokers)) * usize::from(decks);
+        assert!(
+            players * hand.len() <= deck_size,
+            "not enough cards to deal every player a hand"
+        );
+        let mut unseen: Vec<Card> = StandardDeckBuilder::new()
+            .jokers(self.jokers.into())
+            .subdecks(decks.into())
+            .build()
+            .draw_n(deck_size)
+            .collect();
+        for card in hand {
+            if let Some(position) = unseen.iter().position(|other| other == card) {
+                unseen.swap_remove(position);
+            }
+        }
+
+        let mut counts = vec![0; hand.len() + 1];
+        for _ in 0..self.samples {
+            unseen.shuffle(&mut self.rng);
+            let mut others = unseen.chunks(hand.len());
+            let mut hands: Vec<Player> = (0..players)
+                .map(|other| {
+                    let mut player = Player::new();
+                    if other == seat {
+                        player.assign(hand.iter().copied());
+                    } else if let Some(cards) = others.next() {
+                        player.assign(cards.iter().copied());
+                    }
+                    player
+                })
+                .collect();
+            let tricks = play_out(&mut hands, trump_suit, self.duplicates);
+            counts[usize::from(tricks[seat])] += 1;
+        }
+        TrickDistribution {
+            probabilities: counts
+                .into_iter()
+                .map(|count| f64::from(count) / self.samples.max(1) as f64)
+                .collect(),
+        }
+    }
+
+    /// The distribution of the tricks `player` wins in the current round of
+    /// `game`, judged from their hand alone, or `None` if the cards of the
+    /// round have not been dealt yet.
+    ///
+    /// The jokers and the rule for identical cards are taken from the
+    /// game's config.
+    pub fn evaluate_player(&mut self, game: &Judgment, player: usize) -> Option<TrickDistribution> {
+        let (Stage::PredictScores(round) | Stage::Play(round) | Stage::Claim(round, _)) =
+            &game.stage
+        else {
+            return None;
+        };
+        let hand = game.players.get(player)?.hand();
+        let players = game.players.len();
+        let config = game.config();
+        let mut evaluator = HandEvaluator {
+            samples: self.samples,
+            jokers: config.jokers(),
+            duplicates: config.duplicate_cards(),
+            rng: self.rng.clone(),
+        };
+        // later in the round, the hand is only part of what was dealt, but
+        // the player still sits in the same place relative to the leader
+        let distribution = evaluator.evaluate(
+            hand,
+            round.trump_suit.as_ref(),
+            config.players(),
+            (player + players - round.leader) % players,
+            config.decks(),
+        );
+        self.rng = evaluator.rng;
+        Some(distribution)
+    }
+}
+
+/// How likely a hand is to win every number of tricks, from none to one per
+/// card.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrickDistribution {
+    probabilities: Vec<f64>,
+}
+
+impl TrickDistribution {
+    /// The probability of winning exactly `tricks[i]` tricks at index `i`.
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// The probability of winning exactly `tricks` tricks.
+    pub fn probability(&self, tricks: u8) -> f64 {
+        self.probabilities
+            .get(usize::from(tricks))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The average number of tricks won.
+    pub fn expected(&self) -> f64 {
+        self.probabilities
+            .iter()
+            .enumerate()
+            .map(|(tricks, probability)| tricks as f64 * probability)
+            .sum()
+    }
+
+    /// The number of tricks most likely to be won, the lowest one on a tie.
+    pub fn most_likely(&self) -> u8 {
+        let mut best = 0;
+        for (tricks, probability) in self.probabilities.iter().enumerate() {
+            if *probability > self.probabilities[best] {
+                best = tricks;
+            }
+        }
+        best as u8
+    }
+}
+
+/// Play every trick of a round in which seat 0 leads the first trick, and
+/// return the tricks won by every seat.
+fn play_out(
+    hands: &mut [Player],
+    trump_suit: Option<&Suit>,
+    duplicates: DuplicateCardRule,
+) -> Vec<u8> {
+    let players = hands.len();
+    let mut tricks = vec![0; players];
+    let mut leader = 0;
+    while !hands[leader].hand().is_empty() {
+        let mut trick: Vec<(usize, Card)> = Vec::with_capacity(players);
+        for seat in (0..players).map(|offset| (leader + offset) % players) {
+            let player = &hands[seat];
+            // the first card that is not a joker decides the suit to follow
+            let mut legal: Vec<Card> = match trick.iter().find(|(_, card)| !card.is_joker()) {
+                Some((_, first)) => player
+                    .hand()
+                    .iter()
+                    .filter(|card| player.follows_suit(card, first))
+                    .copied()
+                    .collect(),
+                None => player.hand().to_vec(),
+            };
+            legal.sort_by_key(|card| strength(card, trump_suit));
+            let card = if trick.is_empty() {
+                legal[legal.len() - 1]
+            } else {
+                let wins = |card: &&Card| {
+                    let mut cards = trick.clone();
+                    cards.push((seat, **card));
+                    trick_winner(&cards, trump_suit, duplicates) == Some(seat)
+                };
+                *legal.iter().find(wins).unwrap_or(&legal[0])
+            };
+            hands[seat].remove(&card);
+            trick.push((seat, card));
+        }
+        leader =
+            trick_winner(&trick, trump_suit, duplicates).expect("a complete trick has a winner");
+        tricks[leader] += 1;
+    }
+    tricks
+}
+
+/// Orders cards by how likely they are to win a trick: jokers first, then
+/// trumps, then by rank.
+fn strength(card: &Card, trump_suit: Option<&Suit>) -> (bool, bool, u8) {
+    let Some(rank) = card.rank() else {
+        return (true, true, 0);
+    };
+    let rank = match rank {
+        Rank::Numeric(value) => *value,
+        Rank::Jack => 11,
+        Rank::Queen => 12,
+        Rank::King => 13,
+        Rank::Ace => 14,
+    };
+    (false, card.suit() == trump_suit, rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use card_deck::standard_deck::{Card, Rank, Suit};
+
+    use crate::{DuplicateCardRule, GameConfig, Judgment, Transition};
+
+    use super::HandEvaluator;
+
+    fn card(suit: Suit, rank: Rank) -> Card {
+        Card::new_normal(suit, rank)
+    }
+
+    #[test]
+    fn test_distribution_of_strong_and_weak_hands() {
+        let mut evaluator = HandEvaluator::new(300, 3);
+        let top_trumps = [
+            card(Suit::Hearts, Rank::Ace),
+            card(Suit::Hearts, Rank::King),
+            card(Suit::Hearts, Rank::Queen),
+        ];
+        let certain = evaluator.evaluate(&top_trumps, Some(&Suit::Hearts), 4, 2, 1);
+        assert_eq!(certain.probabilities(), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(certain.expected(), 3.0);
+        assert_eq!(certain.most_likely(), 3);
+
+        let low_cards = [
+            card(Suit::Clubs, Rank::Numeric(2)),
+            card(Suit::Diamonds, Rank::Numeric(2)),
+            card(Suit::Spades, Rank::Numeric(3)),
+        ];
+        let weak = evaluator.evaluate(&low_cards, Some(&Suit::Hearts), 4, 0, 1);
+        assert_eq!(weak.probabilities().len(), 4);
+        assert!((weak.probabilities().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(weak.expected() < 0.5);
+        assert_eq!(weak.most_likely(), 0);
+
+        let aces = [
+            card(Suit::Clubs, Rank::Ace),
+            card(Suit::Diamonds, Rank::Ace),
+            card(Suit::Spades, Rank::Ace),
+        ];
+        let mixed = evaluator.evaluate(&aces, Some(&Suit::Hearts), 4, 0, 1);
+        assert!(mixed.expected() > weak.expected());
+        assert!(mixed.expected() < certain.expected());
+    }
+
+    #[test]
+    fn test_jokers_and_decks() {
+        // with two decks, the other ace of trumps may beat ours
+        let hand = [card(Suit::Spades, Rank::Ace)];
+        let mut evaluator =
+            HandEvaluator::new(400, 11).duplicate_cards(DuplicateCardRule::LastWins);
+        let doubled = evaluator.evaluate(&hand, Some(&Suit::Spades), 10, 0, 2);
+        assert!(doubled.probability(0) > 0.0);
+        let mut evaluator = HandEvaluator::new(400, 11);
+        assert_eq!(
+            evaluator
+                .evaluate(&hand, Some(&Suit::Spades), 10, 0, 2)
+                .probability(1),
+            1.0
+        );
+        // and so may a joker
+        let mut evaluator = HandEvaluator::new(400, 11).jokers(2);
+        let jokers = evaluator.evaluate(&hand, Some(&Suit::Spades), 5, 0, 1);
+        assert!(jokers.probability(0) > 0.0);
+        // unless ours is the only one
+        let joker = [Card::new_joker()];
+        let mut evaluator = HandEvaluator::new(400, 11).jokers(1);
+        assert_eq!(
+            evaluator.evaluate(&joker, None, 5, 4, 1).probability(1),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_evaluate_player_of_a_game() {
+        let config = GameConfig::builder(3, 5).build().unwrap();
+        let mut game = Judgment::with_config(config);
+        let mut evaluator = HandEvaluator::new(100, 5);
+        assert!(evaluator.evaluate_player(&game, 0).is_none());
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [8; 32] }).unwrap();
+        let distribution = evaluator.evaluate_player(&game, 1).unwrap();
+        assert_eq!(distribution.probabilities().len(), 6);
+        assert!(evaluator.evaluate_player(&game, 3).is_none());
+        // the same seed evaluates the same way
+        let again = HandEvaluator::new(100, 5)
+            .evaluate_player(&game, 1)
+            .unwrap();
+        assert_eq!(again, distribution);
+    }
+
+    #[test]
+    fn test_evaluate_player_mid_trick() {
+        let config = GameConfig::builder(3, 1).build().unwrap();
+        let mut game = Judgment::with_config(config);
+        let mut evaluator = HandEvaluator::new(100, 5);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [8; 32] }).unwrap();
+        for _ in 0..3 {
+            let player = game.current_player().unwrap();
+            game.update(Transition::PredictScore { player, score: 0 })
+                .unwrap();
+        }
+        let leader = game.current_player().unwrap();
+        let card = game.players[leader].hand()[0];
+        game.update(Transition::Play {
+            player: leader,
+            card,
+        })
+        .unwrap();
+        // the leader has played their only card
+        let distribution = evaluator.evaluate_player(&game, leader).unwrap();
+        assert_eq!(distribution.probabilities(), [1.0]);
+        assert_eq!(distribution.expected(), 0.0);
+        let next = game.current_player().unwrap();
+        let distribution = evaluator.evaluate_player(&game, next).unwrap();
+        assert_eq!(distribution.probabilities().len(), 2);
+    }
+}
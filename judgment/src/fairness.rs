@@ -0,0 +1,250 @@
+use sha2::{Digest, Sha256};
+
+use crate::{Judgment, Transition, UnfairDeal};
+
+/// Prefix of the hashed commitments, so that they cannot be mistaken for
+/// any other hash of the same bytes.
+const COMMITMENT_DOMAIN: &[u8] = b"judgment commitment v1";
+/// Prefix of the hash that combines the revealed values into a seed.
+const SEED_DOMAIN: &[u8] = b"judgment seed v1";
+
+/// The commitment to a secret 32 byte `value`, which can be published
+/// without giving the value away and later proves what it was.
+///
+/// It is the SHA-256 hash of a fixed prefix followed by the value. Since the
+/// commitment is all anyone else sees before the reveal, the value must be
+/// unpredictable, e.g. drawn from a cryptographically secure RNG.
+///
+/// # Examples
+/// ```
+/// use judgment::commitment;
+///
+/// let entropy = [42; 32];
+/// assert_eq!(commitment(&entropy), commitment(&entropy));
+/// assert_ne!(commitment(&entropy), commitment(&[43; 32]));
+/// ```
+pub fn commitment(value: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(COMMITMENT_DOMAIN)
+        .chain_update(value)
+        .finalize()
+        .into()
+}
+
+/// Everything needed to check that a deal was shuffled fairly with
+/// commit-reveal dealing.
+///
+/// Before a deal, the dealer commits to a secret seed and every player to
+/// their own secret entropy. Only once all commitments are in are the
+/// values revealed, and the deck is shuffled with [`DealProof::seed`], the
+/// hash of all of them. As long as one player kept their entropy secret
+/// until everybody had committed, nobody could steer the shuffle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DealProof {
+    /// The dealer's commitment to its seed, published first.
+    pub dealer_commitment: [u8; 32],
+    pub dealer_seed: [u8; 32],
+    /// Every player's commitment to their entropy, indexed by player.
+    pub player_commitments: Vec<[u8; 32]>,
+    /// Every player's entropy, indexed by player.
+    pub player_entropy: Vec<[u8; 32]>,
+}
+
+impl DealProof {
+    /// The seed the deal is shuffled with: the SHA-256 hash of a fixed
+    /// prefix, the dealer's seed and the entropy of every player in seating
+    /// order.
+    pub fn seed(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new()
+            .chain_update(SEED_DOMAIN)
+            .chain_update(self.dealer_seed);
+        for entropy in &self.player_entropy {
+            hasher.update(entropy);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Check that every one of the `players` committed to and revealed their
+    /// entropy, and that every revealed value matches its commitment, for the
+    /// `deal`th deal of a game.
+    fn check(&self, deal: usize, players: usize) -> Result<(), UnfairDeal> {
+        if commitment(&self.dealer_seed) != self.dealer_commitment {
+            return Err(UnfairDeal::DealerCommitment { deal });
+        }
+        // without everyone's entropy, the others could have steered the deal
+        if self.player_commitments.len() != players || self.player_entropy.len() != players {
+            return Err(UnfairDeal::MissingEntropy { deal });
+        }
+        for (player, (entropy, committed)) in self
+            .player_entropy
+            .iter()
+            .zip(&self.player_commitments)
+            .enumerate()
+        {
+            if commitment(entropy) != *committed {
+                return Err(UnfairDeal::PlayerCommitment { deal, player });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Judgment {
+    /// Check that the deals of the game so far, in order, were shuffled with
+    /// the seeds of the `proofs`, that every player of the game added their
+    /// entropy to each of them, and that every value of the proofs matches
+    /// its commitment. The seed decides every card dealt, and the card turned
+    /// up with [`TrumpSelection::TurnUp`](crate::TrumpSelection::TurnUp).
+    ///
+    /// # Errors
+    /// Errors with the first deal that cannot be verified, or if there is
+    /// not exactly one proof per deal.
+    ///
+    /// # Examples
+    /// ```
+    /// use judgment::{commitment, DealProof, Judgment, Transition};
+    ///
+    /// let entropy = vec![[1; 32], [2; 32]];
+    /// let proof = DealProof {
+    ///     dealer_commitment: commitment(&[9; 32]),
+    ///     dealer_seed: [9; 32],
+    ///     player_commitments: entropy.iter().map(commitment).collect(),
+    ///     player_entropy: entropy,
+    /// };
+    /// let mut game = Judgment::new(2, 3, None);
+    /// game.start().unwrap();
+    /// game.update(Transition::Deal { seed: proof.seed() }).unwrap();
+    /// assert!(game.verify_deals(&[proof]).is_ok());
+    /// ```
+    pub fn verify_deals(&self, proofs: &[DealProof]) -> Result<(), UnfairDeal> {
+        let seeds: Vec<[u8; 32]> = self
+            .history
+            .iter()
+            .filter_map(|transition| match transition {
+                Transition::Deal { seed } => Some(*seed),
+                _ => None,
+            })
+            .collect();
+        if seeds.len() != proofs.len() {
+            return Err(UnfairDeal::DealCount {
+                deals: seeds.len(),
+                proofs: proofs.len(),
+            });
+        }
+        for (deal, (seed, proof)) in seeds.iter().zip(proofs).enumerate() {
+            proof.check(deal, self.players.len())?;
+            if proof.seed() != *seed {
+                return Err(UnfairDeal::SeedMismatch { deal });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Judgment, RandomDriver, Transition, UnfairDeal};
+
+    use super::{commitment, DealProof};
+
+    fn proof(dealer_seed: [u8; 32], player_entropy: Vec<[u8; 32]>) -> DealProof {
+        DealProof {
+            dealer_commitment: commitment(&dealer_seed),
+            dealer_seed,
+            player_commitments: player_entropy.iter().map(commitment).collect(),
+            player_entropy,
+        }
+    }
+
+    #[test]
+    fn test_every_input_changes_the_seed() {
+        let base = proof([0; 32], vec![[1; 32], [2; 32]]);
+        let seeds = [
+            base.seed(),
+            proof([3; 32], vec![[1; 32], [2; 32]]).seed(),
+            proof([0; 32], vec![[1; 32], [3; 32]]).seed(),
+            // the order of the players matters
+            proof([0; 32], vec![[2; 32], [1; 32]]).seed(),
+        ];
+        for (index, seed) in seeds.iter().enumerate() {
+            assert!(seeds[index + 1..].iter().all(|other| other != seed));
+        }
+        assert_ne!(commitment(&[1; 32]), [1; 32]);
+        assert_eq!(base.seed(), proof([0; 32], vec![[1; 32], [2; 32]]).seed());
+    }
+
+    #[test]
+    fn test_verify_deals() {
+        let mut game = Judgment::new(3, 2, None);
+        game.start().unwrap();
+        let mut driver = RandomDriver::new(4);
+        let mut proofs = Vec::new();
+        while !game.is_over() {
+            let mut transition = driver.next_transition(&game).unwrap();
+            if let Transition::Deal { .. } = transition {
+                let deal = proofs.len() as u8;
+                let fair = proof([deal; 32], vec![[10; 32], [20 + deal; 32], [30; 32]]);
+                transition = Transition::Deal { seed: fair.seed() };
+                proofs.push(fair);
+            }
+            game.update(transition).unwrap();
+            assert_eq!(game.verify_deals(&proofs), Ok(()));
+        }
+        assert_eq!(
+            game.verify_deals(&proofs[1..]),
+            Err(UnfairDeal::DealCount {
+                deals: 2,
+                proofs: 1
+            })
+        );
+
+        let mut tampered = proofs.clone();
+        tampered[1].player_entropy[2] = [31; 32];
+        assert_eq!(
+            game.verify_deals(&tampered),
+            Err(UnfairDeal::PlayerCommitment { deal: 1, player: 2 })
+        );
+        // a new commitment for the new entropy still gives a different seed
+        tampered[1].player_commitments[2] = commitment(&[31; 32]);
+        assert_eq!(
+            game.verify_deals(&tampered),
+            Err(UnfairDeal::SeedMismatch { deal: 1 })
+        );
+        let mut tampered = proofs.clone();
+        tampered[0].dealer_seed = [7; 32];
+        assert_eq!(
+            game.verify_deals(&tampered),
+            Err(UnfairDeal::DealerCommitment { deal: 0 })
+        );
+        let mut tampered = proofs.clone();
+        tampered[0].player_entropy.pop();
+        assert_eq!(
+            game.verify_deals(&tampered),
+            Err(UnfairDeal::MissingEntropy { deal: 0 })
+        );
+        // every player has to add their entropy, not just some of them
+        let mut tampered = proofs;
+        tampered[0].player_entropy.pop();
+        tampered[0].player_commitments.pop();
+        assert_eq!(
+            game.verify_deals(&tampered),
+            Err(UnfairDeal::MissingEntropy { deal: 0 })
+        );
+    }
+
+    #[test]
+    fn test_dealer_alone_is_not_fair() {
+        let dealer_only = proof([5; 32], Vec::new());
+        let mut game = Judgment::new(3, 2, None);
+        game.start().unwrap();
+        game.update(Transition::Deal {
+            seed: dealer_only.seed(),
+        })
+        .unwrap();
+        assert_eq!(
+            game.verify_deals(&[dealer_only]),
+            Err(UnfairDeal::MissingEntropy { deal: 0 })
+        );
+    }
+}
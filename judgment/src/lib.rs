@@ -1,36 +1,86 @@
 use card_deck::standard_deck::{Card, Rank, StandardDeckBuilder, Suit};
-pub use errors::InvalidTransition;
+pub use claim::Claim;
+pub use config::{
+    BiddingMode, ClaimRule, DuplicateCardRule, GameConfig, GameConfigBuilder, HandSizeSchedule,
+    TrumpSelection,
+};
+pub use driver::RandomDriver;
+#[cfg(feature = "serde")]
+pub use errors::SnapshotError;
+pub use errors::{
+    GameConfigError, InvalidPosition, InvalidRewind, InvalidTransition, NotationError,
+    NotationErrorKind, UnfairDeal,
+};
+pub use evaluator::{HandEvaluator, TrickDistribution};
+pub use fairness::{commitment, DealProof};
+pub use notation::GameRecord;
 use player::Player;
 use rand::SeedableRng;
+pub use result::{GameResult, PlayerStats, RoundResult, Standing};
+pub use scoring::{ExactOrLose, PenaltyPerTrick, Scoring, ScoringRule, TenPlusBid, ZeroBidBonus};
+#[cfg(feature = "serde")]
+pub use snapshot::SNAPSHOT_VERSION;
+pub use solver::DoubleDummy;
+pub use teams::{TeamBid, Teams};
+pub use view::PlayerView;
 
+mod claim;
+mod config;
+mod driver;
 mod errors;
+mod evaluator;
+mod fairness;
+mod notation;
 mod player;
+mod result;
+mod scoring;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod solver;
+mod teams;
+mod view;
 
 /// The Game
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Judgment {
     stage: Stage,
     players: Vec<Player>,
     trick: Trick,
+    /// The first card of the current trick that is not a joker, which
+    /// decides the suit to follow.
     first_of_trick: Option<Card>,
     scores: Vec<i64>,
-    decks: u8,
-    player_count: u8,
-    starting_hand_size: u8,
+    /// The score of every team, empty unless the players play in teams.
+    team_scores: Vec<i64>,
+    config: GameConfig,
     history: Vec<Transition>,
+    round_results: Vec<RoundResult>,
 }
 
 pub type Trick = Vec<Option<Card>>;
 
+/// A trick that has been played to completion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompletedTrick {
+    /// The player who played the first card.
+    pub leader: usize,
+    /// The players and the cards they played, in the order they were played.
+    pub cards: Vec<(usize, Card)>,
+    /// The player who won the trick.
+    pub winner: usize,
+}
+
 impl Judgment {
     /// Create a new game of Judgment for `players` and first round having
     /// `starting_hand_size` cards per player. Optionally, also specify how many
-    /// card decks should be used.
+    /// card decks should be used. All the other rules are the defaults of
+    /// [`GameConfig::builder`].
     ///
     /// # Panics
-    /// - Panics if the specified number of decks are insufficient to deal enough
-    /// cards for the first round.
-    /// - Panics if the `starting_hand_size` is larger than 13.
+    /// Panics if the parameters are invalid, see [`Judgment::try_new`] for a
+    /// fallible version.
     ///
     /// # Examples
     /// ```
@@ -44,39 +94,144 @@ impl Judgment {
     /// Judgment::new(5, 13, Some(1));
     /// ```
     pub fn new(players: u8, starting_hand_size: u8, decks: Option<u8>) -> Self {
-        assert!(
-            starting_hand_size <= 13,
-            "cannot start with more than 13 cards per player"
-        );
-        // SAFETY
-        // cannot overflow because 13 * u8::MAX / 52 + 1 == 64
-        let estimated_decks = starting_hand_size * players / 52
-            + u8::from((u16::from(starting_hand_size) * u16::from(players)) % 52 != 0);
-        let actual_decks = if let Some(decks) = decks {
-            assert!(estimated_decks <= decks, "{decks} decks are not enough for the give number of players and starting hand size");
-            decks
-        } else {
-            estimated_decks
-        };
+        Judgment::try_new(players, starting_hand_size, decks).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Create a new game of Judgment for `players` and first round having
+    /// `starting_hand_size` cards per player. Optionally, also specify how many
+    /// card decks should be used. All the other rules are the defaults of
+    /// [`GameConfig::builder`].
+    ///
+    /// # Errors
+    /// - Errors if there are no players.
+    /// - Errors if the `starting_hand_size` is 0 or larger than 13.
+    /// - Errors if there are too many players for the `starting_hand_size`.
+    /// - Errors if the specified number of decks are insufficient to deal enough
+    ///   cards for the first round.
+    ///
+    /// # Examples
+    /// ```
+    /// use judgment::{GameConfigError, Judgment};
+    ///
+    /// assert!(Judgment::try_new(4, 13, None).is_ok());
+    /// assert_eq!(
+    ///     Judgment::try_new(5, 13, Some(1)).unwrap_err(),
+    ///     GameConfigError::TooFewDecks { decks: 1, required: 2 }
+    /// );
+    /// ```
+    pub fn try_new(
+        players: u8,
+        starting_hand_size: u8,
+        decks: Option<u8>,
+    ) -> Result<Self, GameConfigError> {
+        let mut builder = GameConfig::builder(players, starting_hand_size);
+        if let Some(decks) = decks {
+            builder = builder.decks(decks);
+        }
+        builder.build().map(Judgment::with_config)
+    }
+
+    /// Create a new game of Judgment played with the rules in `config`.
+    ///
+    /// # Examples
+    /// ```
+    /// use judgment::{GameConfig, HandSizeSchedule, Judgment};
+    ///
+    /// let config = GameConfig::builder(4, 7)
+    ///     .hand_sizes(HandSizeSchedule::UpAndDown)
+    ///     .last_player_restriction(false)
+    ///     .build()
+    ///     .unwrap();
+    /// Judgment::with_config(config);
+    /// ```
+    pub fn with_config(config: GameConfig) -> Self {
+        let players = usize::from(config.players());
         Judgment {
             stage: Stage::PrePlay,
-            players: vec![Player::new(); usize::from(players)],
-            trick: vec![None; usize::from(players)],
+            players: vec![Player::new(); players],
+            trick: vec![None; players],
             first_of_trick: None,
-            scores: vec![0; usize::from(players)],
-            decks: actual_decks,
-            player_count: players,
-            starting_hand_size,
+            scores: vec![0; players],
+            team_scores: vec![0; config.teams().map_or(0, Teams::count)],
+            config,
             history: Vec::new(),
+            round_results: Vec::new(),
         }
     }
 
+    /// Recreate a game from its rules and the log of transitions it went
+    /// through, as returned by [`Judgment::history`].
+    ///
+    /// The game is started before the transitions are applied. Since
+    /// [`Transition::Deal`] carries the seed used for shuffling, the replayed
+    /// game ends up in exactly the same state as the original one.
+    ///
+    /// # Errors
+    /// Errors with the first [`InvalidTransition`] encountered while applying
+    /// the `transitions`.
+    pub fn replay(
+        config: GameConfig,
+        transitions: &[Transition],
+    ) -> Result<Self, InvalidTransition> {
+        let mut game = Judgment::with_config(config);
+        game.start()?;
+        for transition in transitions {
+            game.update(*transition)?;
+        }
+        Ok(game)
+    }
+
     /// Try to advance the game with the `transition`.
+    ///
+    /// On success, returns what changed in the order it happened:
+    /// - [`Transition::Deal`] gives [`StateUpdate::RoundStarted`] then
+    ///   [`StateUpdate::CardsDealt`], followed by
+    ///   [`StateUpdate::TrumpTurnedUp`] with [`TrumpSelection::TurnUp`].
+    /// - [`Transition::PredictScore`] and [`Transition::PredictBlind`] give
+    ///   [`StateUpdate::Predictions`], or [`StateUpdate::PredictionSealed`]
+    ///   with [`BiddingMode::Sealed`]. With sealed bidding, the last
+    ///   prediction, or a deal that leaves nobody to predict, is followed by
+    ///   [`StateUpdate::Predictions`] revealing them all.
+    /// - [`Transition::Play`] gives [`StateUpdate::Trick`]; if that completed
+    ///   the trick, [`StateUpdate::TrickWon`] and [`StateUpdate::RoundScores`];
+    ///   if that also completed the round, [`StateUpdate::RoundEnded`],
+    ///   [`StateUpdate::GameScores`] and, when playing in teams,
+    ///   [`StateUpdate::TeamScores`]; and if that was the last round,
+    ///   [`StateUpdate::GameOver`].
+    /// - [`Transition::Claim`] gives [`StateUpdate::ClaimMade`],
+    ///   [`Transition::AcceptClaim`] [`StateUpdate::ClaimAccepted`] and
+    ///   [`Transition::RejectClaim`] [`StateUpdate::ClaimRejected`]. Once a
+    ///   claim is settled, they are followed by [`StateUpdate::RoundScores`]
+    ///   and everything that follows a completed round.
+    ///
+    /// Whenever it is some player's turn afterwards, the updates end with
+    /// [`StateUpdate::TurnChanged`].
+    ///
+    /// # Errors
+    /// Errors if [`Judgment::validate`] rejects the `transition`, in which
+    /// case the game is left untouched.
     pub fn update(
         &mut self,
         transition: Transition,
     ) -> Result<Vec<StateUpdate>, InvalidTransition> {
-        let res = match (&mut self.stage, transition) {
+        self.validate(&transition)?;
+        let mut updates = self.apply(transition);
+        if let Some(player) = self.current_player() {
+            updates.push(StateUpdate::TurnChanged(player));
+        }
+        self.history.push(transition);
+        Ok(updates)
+    }
+
+    /// Check whether the game would accept the `transition` in its current
+    /// state, without changing anything.
+    ///
+    /// [`Judgment::update`] succeeds exactly when this does.
+    ///
+    /// # Errors
+    /// Errors with the reason the `transition` would be rejected.
+    pub fn validate(&self, transition: &Transition) -> Result<(), InvalidTransition> {
+        match (&self.stage, *transition) {
             // invalid combinations
             (Stage::PrePlay, Transition::Deal { .. }) => {
                 Err(InvalidTransition::DealBeforeGameStart)
@@ -87,9 +242,20 @@ impl Judgment {
             (Stage::PrePlay, Transition::PredictScore { .. }) => {
                 Err(InvalidTransition::PredictBeforeDeal)
             }
+            (Stage::PrePlay, Transition::PredictBlind { .. }) => {
+                Err(InvalidTransition::PredictBeforeDeal)
+            }
             (Stage::Deal(_), Transition::Play { .. }) => {
                 Err(InvalidTransition::PlayBeforeScorePrediction)
             }
+            (
+                Stage::PrePlay | Stage::Deal(_) | Stage::PredictScores(_),
+                Transition::Claim { .. },
+            ) => Err(InvalidTransition::PlayBeforeScorePrediction),
+            (
+                Stage::PrePlay | Stage::Deal(_) | Stage::PredictScores(_) | Stage::Play(_),
+                Transition::AcceptClaim { .. } | Transition::RejectClaim { .. },
+            ) => Err(InvalidTransition::NoPendingClaim),
             (Stage::Deal(_), Transition::PredictScore { .. }) => {
                 Err(InvalidTransition::PredictBeforeDeal)
             }
@@ -97,142 +263,344 @@ impl Judgment {
             (Stage::PredictScores(_), Transition::Play { .. }) => {
                 Err(InvalidTransition::PlayBeforeScorePrediction)
             }
-            (Stage::Play(_), Transition::Deal { .. }) => Err(InvalidTransition::ReDeal),
-            (Stage::Play(_), Transition::PredictScore { .. }) => Err(InvalidTransition::RePredict),
-            (Stage::Over { .. }, _) => Err(InvalidTransition::GameOver),
-            // valid combinations
-            (Stage::Deal(round), Transition::Deal { seed }) => {
-                let hand_size = round.hand_size;
-                self.stage = Stage::PredictScores(round.clone());
-                self.deal(hand_size, seed);
-                Ok(vec![StateUpdate::CardsDealt])
+            (Stage::Play(_) | Stage::Claim(..), Transition::Deal { .. }) => {
+                Err(InvalidTransition::ReDeal)
             }
-            (Stage::PredictScores(round), Transition::PredictScore { player, score }) => {
-                if round.player != player {
+            (Stage::Play(_) | Stage::Claim(..), Transition::PredictScore { .. }) => {
+                Err(InvalidTransition::RePredict)
+            }
+            (
+                Stage::PredictScores(_) | Stage::Play(_) | Stage::Claim(..),
+                Transition::PredictBlind { .. },
+            ) => Err(InvalidTransition::BlindPredictionAfterDeal),
+            (Stage::Claim(..), Transition::Play { .. } | Transition::Claim { .. }) => {
+                Err(InvalidTransition::ClaimPending)
+            }
+            (Stage::Over, _) => Err(InvalidTransition::GameOver),
+            // valid combinations, as long as the details check out
+            (Stage::Deal(_), Transition::Deal { .. }) => Ok(()),
+            (Stage::Deal(round), Transition::PredictBlind { player, score }) => {
+                if self.config.blind_multiplier().is_none() {
+                    return Err(InvalidTransition::BlindPredictionNotAllowed);
+                }
+                if player >= round.predicted_scores.len() {
                     return Err(InvalidTransition::OutOfTurnPlay);
                 }
+                if round.has_predicted(player) {
+                    return Err(InvalidTransition::RePredict);
+                }
                 if score > round.hand_size {
                     return Err(InvalidTransition::PredictionOutOfRange);
                 }
-                if round
-                    .predicted_scores
-                    .iter()
-                    .filter(|score| score.is_some())
-                    .count()
-                    == usize::from(self.player_count - 1)
+                if self.config.last_player_restriction()
+                    && round.forbidden_prediction(self.config.teams()) == Some(score)
                 {
-                    let prediction_sum = round
-                        .predicted_scores
-                        .iter()
-                        .filter_map(|opt_v| opt_v.map(u16::from))
-                        .sum::<u16>();
-                    if prediction_sum + u16::from(score) == u16::from(round.hand_size) {
-                        return Err(InvalidTransition::LastPlayerPrediction);
+                    return Err(InvalidTransition::LastPlayerPrediction);
+                }
+                Ok(())
+            }
+            (Stage::PredictScores(round), Transition::PredictScore { player, score }) => {
+                match self.config.bidding() {
+                    BiddingMode::Sequential if round.player != player => {
+                        return Err(InvalidTransition::OutOfTurnPlay);
+                    }
+                    BiddingMode::Sealed if player >= round.predicted_scores.len() => {
+                        return Err(InvalidTransition::OutOfTurnPlay);
                     }
+                    BiddingMode::Sealed if round.has_predicted(player) => {
+                        return Err(InvalidTransition::RePredict);
+                    }
+                    _ => (),
                 }
-                round.predicted_scores[player] = Some(score);
-                let return_val = StateUpdate::Predictions(round.predicted_scores.clone());
-                // SAFETY
-                // `as` conversion is fine because `Round { player }` < `Judgment { player_count: u8 }`
-                round.player = usize::from((round.player as u8 + 1) % self.player_count);
-                if round
-                    .predicted_scores
-                    .iter()
-                    .filter(|score| score.is_some())
-                    .count()
-                    == usize::from(self.player_count)
+                if score > round.hand_size {
+                    return Err(InvalidTransition::PredictionOutOfRange);
+                }
+                if self.config.last_player_restriction()
+                    && round.forbidden_prediction(self.config.teams()) == Some(score)
                 {
-                    self.stage = Stage::Play(round.clone());
+                    return Err(InvalidTransition::LastPlayerPrediction);
                 }
-                Ok(vec![return_val])
+                Ok(())
             }
             (Stage::Play(round), Transition::Play { player, card }) => {
                 if round.player != player {
                     return Err(InvalidTransition::OutOfTurnPlay);
                 }
+                if self.players[player].search(&card).is_none() {
+                    return Err(InvalidTransition::NoSuchPlayerCard);
+                }
                 if let Some(first_card) = &self.first_of_trick {
-                    if card.suit() != first_card.suit()
-                        && self.players[player].has_suit(first_card.suit().unwrap())
-                    {
+                    if !self.players[player].follows_suit(&card, first_card) {
                         return Err(InvalidTransition::SuitMismatch);
                     }
-                } else {
-                    self.first_of_trick = Some(card);
                 }
-                if self.players[player].remove(&card).is_none() {
-                    return Err(InvalidTransition::NoSuchPlayerCard);
+                Ok(())
+            }
+            (Stage::Play(round), Transition::Claim { player }) => {
+                self.validate_claim(round, player)
+            }
+            (Stage::Claim(_, claim), Transition::AcceptClaim { player }) => {
+                self.validate_claim_answer(claim, player, true)
+            }
+            (Stage::Claim(_, claim), Transition::RejectClaim { player }) => {
+                self.validate_claim_answer(claim, player, false)
+            }
+        }
+    }
+
+    /// Apply a `transition` that has already passed [`Judgment::validate`].
+    fn apply(&mut self, transition: Transition) -> Vec<StateUpdate> {
+        match (&mut self.stage, transition) {
+            (Stage::Deal(round), Transition::Deal { seed }) => {
+                let hand_size = round.hand_size;
+                let mut round = round.clone();
+                let next_card = self.deal(hand_size, seed);
+                let turn_up = self.config.trump_selection() == TrumpSelection::TurnUp;
+                if turn_up {
+                    round.turned_up = next_card;
+                    round.trump_suit = next_card.and_then(|card| card.suit().copied());
+                }
+                let mut updates = vec![
+                    StateUpdate::RoundStarted {
+                        hand_size,
+                        trump: round.trump_suit,
+                        starting_player: round.player,
+                    },
+                    StateUpdate::CardsDealt,
+                ];
+                if turn_up {
+                    updates.push(StateUpdate::TrumpTurnedUp(next_card));
+                }
+                let starting_player = round.starting_player;
+                updates.extend(self.await_prediction(round, starting_player));
+                updates
+            }
+            (Stage::Deal(round), Transition::PredictBlind { player, score }) => {
+                for seat in predicting_seats(&self.config, player) {
+                    round.blind[seat] = true;
+                }
+                vec![round.record_prediction(player, score, &self.config)]
+            }
+            (Stage::PredictScores(round), Transition::PredictScore { player, score }) => {
+                let update = round.record_prediction(player, score, &self.config);
+                let next = match self.config.bidding() {
+                    BiddingMode::Sequential => player + 1,
+                    BiddingMode::Sealed => round.starting_player,
+                };
+                let round = round.clone();
+                let mut updates = vec![update];
+                updates.extend(self.await_prediction(round, next));
+                updates
+            }
+            (Stage::Play(round), Transition::Play { player, card }) => {
+                if self.trick.iter().all(Option::is_none) {
+                    round.leader = player;
+                }
+                if self.first_of_trick.is_none() && !card.is_joker() {
+                    self.first_of_trick = Some(card);
                 }
+                self.players[player].remove(&card);
                 self.trick[player] = Some(card);
                 let trick_update = StateUpdate::Trick(self.trick.clone());
                 let mut return_val = vec![trick_update];
                 // SAFETY
                 // `as` conversion is fine because `Round { player }` < `Judgment { player_count: u8 }`
-                round.player = usize::from((round.player as u8 + 1) % self.player_count);
+                round.player = usize::from((round.player as u8 + 1) % self.config.players());
                 // The play ends ^ here. The rest is for updating the state.
                 // check whether the winning player should be updated.
-                if trick_card_comparator(
-                    &self.trick[round.potential_winner].unwrap(),
-                    &card,
+                let played: Vec<(usize, Card)> = (0..self.trick.len())
+                    .map(|offset| (round.leader + offset) % self.trick.len())
+                    .filter_map(|seat| self.trick[seat].map(|card| (seat, card)))
+                    .collect();
+                round.potential_winner = trick_winner(
+                    &played,
                     round.trump_suit.as_ref(),
+                    self.config.duplicate_cards(),
                 )
-                .is_lt()
-                {
-                    round.potential_winner = player;
-                }
+                .expect("a card was just played");
                 // check whether current trick turn is complete.
                 if self.trick.iter().filter(|card| card.is_some()).count()
-                    == self.player_count.into()
+                    == usize::from(self.config.players())
                 {
                     round.trick_scores[round.potential_winner] += 1;
+                    let player_count = self.trick.len();
+                    round.completed_tricks.push(CompletedTrick {
+                        leader: round.leader,
+                        cards: (0..player_count)
+                            .map(|offset| (round.leader + offset) % player_count)
+                            .map(|seat| (seat, self.trick[seat].unwrap()))
+                            .collect(),
+                        winner: round.potential_winner,
+                    });
+                    return_val.push(StateUpdate::TrickWon {
+                        winner: round.potential_winner,
+                        cards: self.trick.clone(),
+                    });
                     return_val.push(StateUpdate::RoundScores(round.trick_scores.clone()));
                     round.player = round.potential_winner;
+                    round.leader = round.potential_winner;
                     self.trick.iter_mut().for_each(|card| *card = None);
                     self.first_of_trick.take();
                     // check whether the whole round is over.
                     if self.players[0].hand().is_empty() {
-                        for (idx, score) in round.trick_scores.iter().enumerate() {
-                            if round.predicted_scores[idx]
-                                .is_some_and(|prediction| prediction == *score)
-                            {
-                                self.scores[idx] +=
-                                    i64::from(round.predicted_scores[idx].take().unwrap()).max(1);
-                            } else {
-                                self.scores[idx] -=
-                                    i64::from(round.predicted_scores[idx].take().unwrap()).max(1);
-                            }
-                        }
-                        return_val.push(StateUpdate::GameScores(self.scores.clone()));
-                        if round.hand_size == 1 {
-                            self.stage = Stage::Over;
-                        } else {
-                            self.stage = Stage::Deal(Round {
-                                player: round.player,
-                                potential_winner: round.player,
-                                hand_size: round.hand_size - 1,
-                                trump_suit: match round.trump_suit {
-                                    Some(suit) => match suit {
-                                        Suit::Diamonds => None,
-                                        Suit::Clubs => Some(Suit::Diamonds),
-                                        Suit::Hearts => Some(Suit::Clubs),
-                                        Suit::Spades => Some(Suit::Hearts),
-                                    },
-                                    None => Some(Suit::Spades),
-                                },
-                                predicted_scores: vec![None; usize::from(self.player_count)],
-                                trick_scores: vec![0; self.player_count.into()],
-                                starting_player: (round.starting_player + 1)
-                                    % usize::from(self.player_count),
-                            });
-                        }
+                        let round = round.clone();
+                        return_val.extend(self.end_round(round));
                     }
                 }
-                Ok(return_val)
+                return_val
             }
-        };
-        if res.is_ok() {
-            self.history.push(transition);
+            (Stage::Play(round), Transition::Claim { player }) => {
+                let round = round.clone();
+                self.make_claim(round, player)
+            }
+            (Stage::Claim(round, claim), Transition::AcceptClaim { player }) => {
+                claim.accepted[player] = true;
+                let mut updates = vec![StateUpdate::ClaimAccepted { player }];
+                if claim.accepted.iter().all(|accepted| *accepted) {
+                    let (round, claim) = (round.clone(), claim.clone());
+                    updates.extend(self.settle_claim(round, &claim));
+                }
+                updates
+            }
+            (Stage::Claim(round, _), Transition::RejectClaim { player }) => {
+                self.stage = Stage::Play(round.clone());
+                vec![StateUpdate::ClaimRejected { player }]
+            }
+            _ => unreachable!("validate rejects every other combination"),
         }
-        res
+    }
+
+    /// Score the `round` whose tricks have all been won and move on to the
+    /// next round, or end the game after the last one.
+    fn end_round(&mut self, mut round: Round) -> Vec<StateUpdate> {
+        let mut updates = Vec::new();
+        let predictions: Vec<u8> = round
+            .predicted_scores
+            .iter_mut()
+            .map(|prediction| prediction.take().unwrap())
+            .collect();
+        let mut deltas = self
+            .config
+            .scoring()
+            .settle(&predictions, &round.trick_scores);
+        if let Some(multiplier) = self.config.blind_multiplier() {
+            for (delta, blind) in deltas.iter_mut().zip(&round.blind) {
+                if *blind {
+                    *delta *= i64::from(multiplier);
+                }
+            }
+        }
+        let mut team_deltas = Vec::new();
+        if let Some(teams) = self.config.teams() {
+            team_deltas = self.config.scoring().settle(
+                &teams.predictions(&predictions),
+                &teams.tricks(&round.trick_scores),
+            );
+            if let Some(multiplier) = self.config.blind_multiplier() {
+                for (team, delta) in team_deltas.iter_mut().enumerate() {
+                    if teams.members(team).all(|seat| round.blind[seat]) {
+                        *delta *= i64::from(multiplier);
+                    }
+                }
+            }
+            if teams.bid() == TeamBid::Joint {
+                deltas = (0..deltas.len())
+                    .map(|seat| team_deltas[teams.team_of(seat)])
+                    .collect();
+            }
+            for (score, delta) in self.team_scores.iter_mut().zip(&team_deltas) {
+                *score += delta;
+            }
+        }
+        for (score, delta) in self.scores.iter_mut().zip(&deltas) {
+            *score += delta;
+        }
+        self.round_results.push(RoundResult {
+            hand_size: round.hand_size,
+            trump_suit: round.trump_suit,
+            predictions: predictions.clone(),
+            tricks: round.trick_scores.clone(),
+            deltas: deltas.clone(),
+            blind: round.blind.clone(),
+            team_deltas,
+        });
+        updates.push(StateUpdate::RoundEnded {
+            predictions,
+            tricks: round.trick_scores.clone(),
+            deltas,
+            blind: round.blind.clone(),
+        });
+        updates.push(StateUpdate::GameScores(self.scores.clone()));
+        if self.config.teams().is_some() {
+            updates.push(StateUpdate::TeamScores(self.team_scores.clone()));
+        }
+        let next_index = round.index + 1;
+        if let Some(hand_size) = self.config.hand_sizes().get(next_index) {
+            // the dealer moves one seat, and so does the
+            // player to their left who opens the round
+            let starting_player = (round.starting_player + 1) % usize::from(self.config.players());
+            self.stage = Stage::Deal(Round {
+                index: next_index,
+                player: starting_player,
+                potential_winner: starting_player,
+                leader: starting_player,
+                hand_size: *hand_size,
+                trump_suit: self.config.trump_for_round(next_index),
+                turned_up: None,
+                predicted_scores: vec![None; usize::from(self.config.players())],
+                sealed_scores: vec![None; usize::from(self.config.players())],
+                blind: vec![false; usize::from(self.config.players())],
+                trick_scores: vec![0; self.config.players().into()],
+                starting_player,
+                completed_tricks: Vec::new(),
+            });
+        } else {
+            self.stage = Stage::Over;
+            updates.push(StateUpdate::GameOver {
+                final_scores: self.scores.clone(),
+                winners: result::winners(&self.scores, self.config.teams(), &self.team_scores),
+            });
+        }
+        updates
+    }
+
+    /// Revert the most recently applied transition.
+    ///
+    /// # Errors
+    /// Errors if no transition has been applied yet.
+    pub fn undo(&mut self) -> Result<Transition, InvalidRewind> {
+        let last = self
+            .history
+            .last()
+            .copied()
+            .ok_or(InvalidRewind::NothingToUndo)?;
+        self.rewind(self.history.len() - 1)?;
+        Ok(last)
+    }
+
+    /// Rewind the game to how it was right after the first `index`
+    /// transitions of its history had been applied. Everything, including the
+    /// hands, the trick, the predictions and the scores, is restored.
+    ///
+    /// # Errors
+    /// Errors if `index` is beyond the length of the history.
+    pub fn rewind(&mut self, index: usize) -> Result<(), InvalidRewind> {
+        if index > self.history.len() {
+            return Err(InvalidRewind::BeyondHistory {
+                requested: index,
+                available: self.history.len(),
+            });
+        }
+        if matches!(self.stage, Stage::PrePlay) {
+            // nothing has happened yet, so nothing to restore
+            return Ok(());
+        }
+        // SAFETY
+        // replaying a prefix of transitions that were accepted by this very
+        // game with the same parameters can never fail
+        *self = Judgment::replay(self.config.clone(), &self.history[..index])
+            .expect("a prefix of the history must replay cleanly");
+        Ok(())
     }
 
     /// Try to start the game.
@@ -243,33 +611,72 @@ impl Judgment {
             return Err(InvalidTransition::Restart);
         }
         let round = Round {
+            index: 0,
             player: 0,
             potential_winner: 0,
-            hand_size: self.starting_hand_size,
-            trump_suit: Some(Suit::Spades),
-            predicted_scores: vec![None; usize::from(self.player_count)],
-            trick_scores: vec![0; self.player_count.into()],
+            leader: 0,
+            hand_size: self.config.hand_sizes()[0],
+            trump_suit: self.config.trump_for_round(0),
+            turned_up: None,
+            predicted_scores: vec![None; usize::from(self.config.players())],
+            sealed_scores: vec![None; usize::from(self.config.players())],
+            blind: vec![false; usize::from(self.config.players())],
+            trick_scores: vec![0; self.config.players().into()],
             starting_player: 0,
+            completed_tricks: Vec::new(),
         };
         self.stage = Stage::Deal(round);
         Ok(())
     }
 
-    fn deal(&mut self, hand_size: u8, random_seed: [u8; 32]) {
+    /// Wait for the first player from seat `next` on who has yet to predict,
+    /// or start the play if everyone has, revealing any sealed predictions.
+    fn await_prediction(&mut self, mut round: Round, next: usize) -> Vec<StateUpdate> {
+        if let Some(player) = round.next_to_predict(next) {
+            round.player = player;
+            self.stage = Stage::PredictScores(round);
+            return Vec::new();
+        }
+        let mut updates = Vec::new();
+        if self.config.bidding() == BiddingMode::Sealed {
+            round.predicted_scores = round.sealed_scores.iter_mut().map(Option::take).collect();
+            updates.push(StateUpdate::Predictions(round.predicted_scores.clone()));
+        }
+        round.player = round.starting_player;
+        self.stage = Stage::Play(round);
+        updates
+    }
+
+    /// Deal `hand_size` cards to every player from a deck shuffled with
+    /// `random_seed`, returning the next card of the deck, if any is left.
+    fn deal(&mut self, hand_size: u8, random_seed: [u8; 32]) -> Option<Card> {
         let mut deck = StandardDeckBuilder::new()
-            .subdecks(self.decks.into())
+            .jokers(self.config.jokers().into())
+            .subdecks(self.config.decks().into())
             .build();
         let mut rng = rand_chacha::ChaCha20Rng::from_seed(random_seed);
         deck.shuffle(&mut rng);
         for player in self.players.iter_mut() {
             player.assign(deck.draw_n(hand_size.into()));
         }
+        deck.draw()
+    }
+
+    /// The rules this game is played with.
+    pub fn config(&self) -> &GameConfig {
+        &self.config
     }
 
     pub fn scores(&self) -> &[i64] {
         &self.scores
     }
 
+    /// All the transitions that have been successfully applied to the game, in
+    /// order.
+    pub fn history(&self) -> &[Transition] {
+        &self.history
+    }
+
     pub fn trick(&self) -> &Trick {
         &self.trick
     }
@@ -278,66 +685,379 @@ impl Judgment {
         self.players.get(player).map(|player| player.hand())
     }
 
+    /// The stage the game is currently in.
+    pub fn phase(&self) -> Phase {
+        match &self.stage {
+            Stage::PrePlay => Phase::NotStarted,
+            Stage::Deal(_) => Phase::Dealing,
+            Stage::PredictScores(_) => Phase::Predicting,
+            Stage::Play(_) => Phase::Playing,
+            Stage::Claim(..) => Phase::Claiming,
+            Stage::Over => Phase::Over,
+        }
+    }
+
+    /// The player who has to predict or play next, if it is anyone's turn.
+    pub fn current_player(&self) -> Option<usize> {
+        match &self.stage {
+            Stage::PrePlay | Stage::Deal(_) | Stage::Claim(..) | Stage::Over => None,
+            Stage::PredictScores(round) | Stage::Play(round) => Some(round.player),
+        }
+    }
+
+    /// The player who deals the current round, if a round is in progress.
+    ///
+    /// The dealer moves one seat to the left every round, starting with the
+    /// last player so that player 0 opens the first round.
+    pub fn dealer(&self) -> Option<usize> {
+        match &self.stage {
+            Stage::PrePlay | Stage::Over => None,
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => Some(round.dealer()),
+        }
+    }
+
+    /// The player who leads, or is about to lead, the current trick, if a
+    /// round is in progress.
+    ///
+    /// The first trick of a round is led by the player left of the dealer,
+    /// every other trick by the winner of the previous one.
+    pub fn leader(&self) -> Option<usize> {
+        match &self.stage {
+            Stage::PrePlay | Stage::Over => None,
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => Some(round.leader),
+        }
+    }
+
+    /// The cards that `player` is allowed to play right now. Empty if it is
+    /// not their turn to play.
+    pub fn legal_cards(&self, player: usize) -> Vec<Card> {
+        match &self.stage {
+            Stage::Play(round) if round.player == player => {
+                let hand = &self.players[player];
+                match &self.first_of_trick {
+                    Some(first_card) => hand
+                        .hand()
+                        .iter()
+                        .filter(|card| hand.follows_suit(card, first_card))
+                        .copied()
+                        .collect(),
+                    None => hand.hand().to_vec(),
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The scores that `player` is allowed to predict right now. Empty if it
+    /// is not their turn to predict; with [`BiddingMode::Sealed`], that is
+    /// once they have predicted.
+    pub fn legal_predictions(&self, player: usize) -> Vec<u8> {
+        match &self.stage {
+            Stage::PredictScores(round) => {
+                let may_predict = match self.config.bidding() {
+                    BiddingMode::Sequential => round.player == player,
+                    BiddingMode::Sealed => {
+                        player < round.predicted_scores.len() && !round.has_predicted(player)
+                    }
+                };
+                if may_predict {
+                    round.allowed_predictions(&self.config)
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The scores that `player` is allowed to predict blind right now, before
+    /// the cards are dealt. Empty if blind predictions are not allowed, the
+    /// cards have been dealt or the player has already predicted.
+    pub fn legal_blind_predictions(&self, player: usize) -> Vec<u8> {
+        match &self.stage {
+            Stage::Deal(round)
+                if self.config.blind_multiplier().is_some()
+                    && player < round.predicted_scores.len()
+                    && !round.has_predicted(player) =>
+            {
+                round.allowed_predictions(&self.config)
+            }
+            _ => Vec::new(),
+        }
+    }
+
     pub fn is_over(&self) -> bool {
         matches!(self.stage, Stage::Over)
     }
 
+    /// The predictions of the current round, if a round is in progress.
+    /// Sealed predictions stay `None` until all of them are revealed.
     pub fn predicted_scores(&self) -> Option<&[Option<u8>]> {
         match &self.stage {
-            Stage::PrePlay | Stage::Deal(_) | Stage::Over => None,
-            Stage::PredictScores(Round {
-                predicted_scores, ..
-            }) => Some(predicted_scores),
-            Stage::Play(Round {
-                predicted_scores, ..
-            }) => Some(predicted_scores),
+            Stage::PrePlay | Stage::Over => None,
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => Some(&round.predicted_scores),
+        }
+    }
+
+    /// The score of every team, if the players play in teams.
+    pub fn team_scores(&self) -> Option<&[i64]> {
+        self.config.teams().map(|_| &self.team_scores[..])
+    }
+
+    /// The tricks won by every team in the current round, if the players
+    /// play in teams and the cards are being played.
+    pub fn team_round_scores(&self) -> Option<Vec<u8>> {
+        let teams = self.config.teams()?;
+        self.round_scores().map(|tricks| teams.tricks(tricks))
+    }
+
+    /// The tricks played to completion in the current round, in the order
+    /// they were played, if a round is in progress.
+    pub fn completed_tricks(&self) -> Option<&[CompletedTrick]> {
+        match &self.stage {
+            Stage::PrePlay | Stage::Over => None,
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => Some(&round.completed_tricks),
         }
     }
 
     pub fn round_scores(&self) -> Option<&[u8]> {
         match &self.stage {
             Stage::PrePlay | Stage::Deal(_) | Stage::PredictScores(_) | Stage::Over => None,
-            Stage::Play(Round { trick_scores, .. }) => Some(trick_scores),
+            Stage::Play(Round { trick_scores, .. })
+            | Stage::Claim(Round { trick_scores, .. }, _) => Some(trick_scores),
+        }
+    }
+
+    /// Number of cards dealt to each player in the current round.
+    pub fn hand_size(&self) -> Option<u8> {
+        match &self.stage {
+            Stage::PrePlay | Stage::Over => None,
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => Some(round.hand_size),
+        }
+    }
+
+    /// The card turned up after the deal to choose the trump suit of the
+    /// current round, if trump is chosen that way and a card was left.
+    pub fn turned_up_card(&self) -> Option<&Card> {
+        match &self.stage {
+            Stage::PrePlay | Stage::Over => None,
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => round.turned_up.as_ref(),
         }
     }
 
     pub fn trump_suit(&self) -> Option<&Suit> {
         match &self.stage {
             Stage::PrePlay | Stage::Over => None,
-            Stage::Deal(round) | Stage::PredictScores(round) | Stage::Play(round) => {
-                round.trump_suit.as_ref()
-            }
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => round.trump_suit.as_ref(),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Round {
+    index: usize,
     player: usize,
     potential_winner: usize,
+    leader: usize,
     hand_size: u8,
     trump_suit: Option<Suit>,
+    /// The card that chose the trump suit, with [`TrumpSelection::TurnUp`].
+    turned_up: Option<Card>,
+    /// Predictions known to everyone.
     predicted_scores: Vec<Option<u8>>,
+    /// Predictions made with [`BiddingMode::Sealed`] that have not been
+    /// revealed yet.
+    sealed_scores: Vec<Option<u8>>,
+    /// The players who predicted before the cards were dealt.
+    blind: Vec<bool>,
     trick_scores: Vec<u8>,
+    /// The player left of the dealer, who predicts first and leads the first
+    /// trick.
     starting_player: usize,
+    completed_tricks: Vec<CompletedTrick>,
+}
+
+impl Round {
+    /// The player who dealt this round, right of the starting player.
+    fn dealer(&self) -> usize {
+        let players = self.trick_scores.len();
+        (self.starting_player + players - 1) % players
+    }
+
+    fn has_predicted(&self, player: usize) -> bool {
+        self.predicted_scores[player].is_some() || self.sealed_scores[player].is_some()
+    }
+
+    /// The first player from seat `seat` on, in turn order, who has yet to
+    /// predict.
+    fn next_to_predict(&self, seat: usize) -> Option<usize> {
+        let players = self.predicted_scores.len();
+        (0..players)
+            .map(|offset| (seat + offset) % players)
+            .find(|player| !self.has_predicted(*player))
+    }
+
+    /// Record the prediction of `player`, for their whole team with
+    /// [`TeamBid::Joint`] and keeping it to themselves with
+    /// [`BiddingMode::Sealed`], and return the update telling everyone about
+    /// it.
+    fn record_prediction(&mut self, player: usize, score: u8, config: &GameConfig) -> StateUpdate {
+        let scores = match config.bidding() {
+            BiddingMode::Sequential => &mut self.predicted_scores,
+            BiddingMode::Sealed => &mut self.sealed_scores,
+        };
+        for seat in predicting_seats(config, player) {
+            scores[seat] = Some(score);
+        }
+        match config.bidding() {
+            BiddingMode::Sequential => StateUpdate::Predictions(self.predicted_scores.clone()),
+            BiddingMode::Sealed => StateUpdate::PredictionSealed { player },
+        }
+    }
+
+    /// The scores the next player to predict may choose from.
+    fn allowed_predictions(&self, config: &GameConfig) -> Vec<u8> {
+        let forbidden = if config.last_player_restriction() {
+            self.forbidden_prediction(config.teams())
+        } else {
+            None
+        };
+        (0..=self.hand_size)
+            .filter(|score| Some(*score) != forbidden)
+            .collect()
+    }
+
+    /// The prediction that the current player may not make because it would
+    /// let the predictions add up to the hand size, if they are the last to
+    /// predict. With [`TeamBid::Joint`], that is the last team to predict.
+    fn forbidden_prediction(&self, teams: Option<&Teams>) -> Option<u8> {
+        let predictions: Vec<Option<u8>> = match teams {
+            Some(teams) if teams.bid() == TeamBid::Joint => (0..teams.count())
+                .map(|team| {
+                    teams
+                        .members(team)
+                        .next()
+                        .and_then(|seat| self.predicted_scores[seat])
+                })
+                .collect(),
+            _ => self.predicted_scores.clone(),
+        };
+        let predicted = predictions.iter().filter(|score| score.is_some()).count();
+        if predicted + 1 != predictions.len() {
+            return None;
+        }
+        let prediction_sum = predictions
+            .iter()
+            .filter_map(|opt_v| opt_v.map(u16::from))
+            .sum::<u16>();
+        u16::from(self.hand_size)
+            .checked_sub(prediction_sum)
+            .and_then(|score| u8::try_from(score).ok())
+    }
+}
+
+/// The seats a prediction by `player` is made for: their whole team with
+/// [`TeamBid::Joint`], only themselves otherwise.
+fn predicting_seats(config: &GameConfig, player: usize) -> Vec<usize> {
+    match config.teams() {
+        Some(teams) if teams.bid() == TeamBid::Joint => {
+            teams.members(teams.team_of(player)).collect()
+        }
+        _ => vec![player],
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum Stage {
     PrePlay,
     Deal(Round),
     PredictScores(Round),
     Play(Round),
+    /// Play is held up by a claim waiting for the opponents.
+    Claim(Round, Claim),
+    Over,
+}
+
+/// The stage a game is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    /// Waiting for [`Judgment::start`].
+    NotStarted,
+    /// Waiting for the cards of the round to be dealt.
+    Dealing,
+    /// Players are predicting how many tricks they will win.
+    Predicting,
+    /// Players are playing their cards.
+    Playing,
+    /// The opponents of a claimant are answering the claim.
+    Claiming,
     Over,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Transition {
-    Deal { seed: [u8; 32] },
-    Play { player: usize, card: Card },
-    PredictScore { player: usize, score: u8 },
+    Deal {
+        seed: [u8; 32],
+    },
+    Play {
+        player: usize,
+        card: Card,
+    },
+    PredictScore {
+        player: usize,
+        score: u8,
+    },
+    /// Predict before the cards of the round are dealt, see
+    /// [`GameConfigBuilder::blind_predictions`].
+    PredictBlind {
+        player: usize,
+        score: u8,
+    },
+    /// Instead of leading the next trick, claim to take every trick left in
+    /// the round. Once the claim is accepted the round ends at once, with
+    /// the claimant credited all of them. How it is accepted depends on the
+    /// [`ClaimRule`].
+    Claim {
+        player: usize,
+    },
+    /// Agree to the pending claim, as an opponent of the claimant.
+    AcceptClaim {
+        player: usize,
+    },
+    /// Turn down the pending claim, as an opponent of the claimant. Play goes
+    /// on with the claimant leading the next trick.
+    RejectClaim {
+        player: usize,
+    },
 }
 
+/// A change in the game caused by a [`Transition`]. See [`Judgment::update`]
+/// for the order in which they are emitted.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StateUpdate {
@@ -345,15 +1065,71 @@ pub enum StateUpdate {
     Predictions(Vec<Option<u8>>),
     RoundScores(Vec<u8>),
     GameScores(Vec<i64>),
+    /// The score of every team, sent along with [`StateUpdate::GameScores`]
+    /// when playing in teams.
+    TeamScores(Vec<i64>),
+    /// The player made a prediction that stays sealed until everyone has
+    /// predicted.
+    PredictionSealed {
+        player: usize,
+    },
     CardsDealt,
+    /// A trick was completed; `cards` is the full trick before it was cleared.
+    TrickWon {
+        winner: usize,
+        cards: Trick,
+    },
+    /// A round was completed and scored. With [`TeamBid::Joint`], every
+    /// player's delta is that of their team.
+    RoundEnded {
+        predictions: Vec<u8>,
+        tricks: Vec<u8>,
+        deltas: Vec<i64>,
+        /// The players who predicted blind, whose deltas were multiplied.
+        blind: Vec<bool>,
+    },
+    /// The cards of a new round are being dealt.
+    RoundStarted {
+        hand_size: u8,
+        trump: Option<Suit>,
+        starting_player: usize,
+    },
+    /// The card after the deal was turned up to choose the trump suit, or
+    /// `None` if every card was dealt.
+    TrumpTurnedUp(Option<Card>),
+    /// It is now this player's turn to predict or play.
+    TurnChanged(usize),
+    /// The player claimed every trick left in the round.
+    ClaimMade {
+        player: usize,
+    },
+    /// The player accepted the pending claim.
+    ClaimAccepted {
+        player: usize,
+    },
+    /// The player turned down the pending claim, which is dropped.
+    ClaimRejected {
+        player: usize,
+    },
+    /// The last round was completed. `winners` are all the players with the
+    /// highest score, or in the teams with the highest score when playing
+    /// in teams.
+    GameOver {
+        final_scores: Vec<i64>,
+        winners: Vec<usize>,
+    },
 }
 
 /// [`Rank::Numeric(2)`] is the lowest and [`Rank::Ace`] is the highest. Suit
 /// ordering has no gameplay significance; it is only meant to arbitrarily order
-/// suits in alternating reds and blacks.
-fn card_comparator(c1: &Card, c2: &Card) -> std::cmp::Ordering {
-    match (c1.suit().unwrap(), c2.suit().unwrap()) {
-        (s1, s2) if s1 == s2 => match (c1.rank().unwrap(), c2.rank().unwrap()) {
+/// suits in alternating reds and blacks. Jokers come after every other card.
+pub fn card_comparator(c1: &Card, c2: &Card) -> std::cmp::Ordering {
+    let (Some((s1, r1)), Some((s2, r2))) = (c1.suit().zip(c1.rank()), c2.suit().zip(c2.rank()))
+    else {
+        return c1.is_joker().cmp(&c2.is_joker());
+    };
+    match (s1, s2) {
+        (s1, s2) if s1 == s2 => match (r1, r2) {
             (r1, r2) if r1 == r2 => std::cmp::Ordering::Equal,
             (Rank::Ace, _) => std::cmp::Ordering::Greater,
             (Rank::Jack, Rank::Queen | Rank::King | Rank::Ace) => std::cmp::Ordering::Less,
@@ -376,14 +1152,19 @@ fn card_comparator(c1: &Card, c2: &Card) -> std::cmp::Ordering {
 
 /// Within the same suit, [`Rank::Numeric(2)`] is the lowest and [`Rank::Ace`]
 /// is the highest. Suits have no ordering except the trump suit, if any, is
-/// better than other suits. If none of the previous conditions can resolve the
-/// ordering, the first played card is better.
-fn trick_card_comparator(
+/// better than other suits, and a joker is better than any card that is not
+/// one. If none of the previous conditions can resolve the ordering, the first
+/// played card is better, except that identical cards, jokers included, are
+/// `Equal`.
+pub fn trick_card_comparator(
     first: &Card,
     second: &Card,
     trump_suit: Option<&Suit>,
 ) -> std::cmp::Ordering {
-    match (first.suit().unwrap(), second.suit().unwrap()) {
+    let (Some(s1), Some(s2)) = (first.suit(), second.suit()) else {
+        return first.is_joker().cmp(&second.is_joker());
+    };
+    match (s1, s2) {
         (s1, s2) if s1 == s2 => card_comparator(first, second),
         (_, s2) => {
             // s1 and s2 are not the same here
@@ -399,13 +1180,1261 @@ fn trick_card_comparator(
     }
 }
 
+/// The player winning a trick so far, given the players and their cards in
+/// the order they were played, or `None` if no card was played.
+///
+/// Cards are compared with [`trick_card_comparator`], except for identical
+/// cards which are settled by `duplicates`.
+pub fn trick_winner(
+    cards: &[(usize, Card)],
+    trump_suit: Option<&Suit>,
+    duplicates: DuplicateCardRule,
+) -> Option<usize> {
+    let (leader, _) = cards.first()?;
+    // a led joker leaves the suit to the first card that is not one
+    let led_suit = cards.iter().find_map(|(_, card)| card.suit());
+    let winner = match duplicates {
+        DuplicateCardRule::FirstWins => cards.iter().reduce(|best, next| {
+            if trick_card_comparator(&best.1, &next.1, trump_suit).is_lt() {
+                next
+            } else {
+                best
+            }
+        }),
+        DuplicateCardRule::LastWins => cards.iter().reduce(|best, next| {
+            if trick_card_comparator(&best.1, &next.1, trump_suit).is_le() {
+                next
+            } else {
+                best
+            }
+        }),
+        DuplicateCardRule::Cancel => cards
+            .iter()
+            .filter(|(_, card)| cards.iter().filter(|(_, other)| other == card).count() == 1)
+            // with the led card cancelled, cards of other suits still cannot
+            // win without trumping
+            .filter(|(_, card)| {
+                card.is_joker() || card.suit() == led_suit || card.suit() == trump_suit
+            })
+            .reduce(|best, next| {
+                if trick_card_comparator(&best.1, &next.1, trump_suit).is_lt() {
+                    next
+                } else {
+                    best
+                }
+            }),
+    };
+    Some(winner.map_or(*leader, |(seat, _)| *seat))
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::Ordering;
 
     use card_deck::standard_deck::{Card, Rank, Suit};
+    use rand::{Rng, SeedableRng};
+
+    use crate::{
+        card_comparator, trick_card_comparator, trick_winner, BiddingMode, DuplicateCardRule,
+        GameConfig, HandSizeSchedule, InvalidRewind, InvalidTransition, Judgment, Phase,
+        RandomDriver, Scoring, ScoringRule, Stage, StateUpdate, TeamBid, Teams, Transition,
+        TrumpSelection,
+    };
+
+    #[test]
+    fn test_replay_reproduces_game() {
+        let mut driver = RandomDriver::new(7);
+        let mut game = Judgment::new(4, 5, None);
+        game.start().unwrap();
+        while !game.is_over() {
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        let replayed = Judgment::replay(game.config.clone(), game.history()).unwrap();
+        assert_eq!(replayed.stage, game.stage);
+        assert_eq!(replayed.players, game.players);
+        assert_eq!(replayed.scores, game.scores);
+        assert_eq!(replayed.history, game.history);
+    }
+
+    #[test]
+    fn test_undo_restores_previous_state() {
+        let mut driver = RandomDriver::new(3);
+        let mut game = Judgment::new(4, 3, None);
+        game.start().unwrap();
+        while !game.is_over() {
+            let before = game.clone();
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+            assert_eq!(game.undo().unwrap(), transition);
+            assert_eq!(game.stage, before.stage);
+            assert_eq!(game.players, before.players);
+            assert_eq!(game.trick, before.trick);
+            assert_eq!(game.first_of_trick, before.first_of_trick);
+            assert_eq!(game.scores, before.scores);
+            assert_eq!(game.history, before.history);
+            game.update(transition).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_completed_tricks() {
+        let mut driver = RandomDriver::new(8);
+        let mut game = Judgment::new(3, 3, None);
+        assert_eq!(game.completed_tricks(), None);
+        game.start().unwrap();
+        let mut played = Vec::new();
+        while !game.is_over() {
+            let transition = driver.next_transition(&game).unwrap();
+            let leader = game.leader();
+            let before = game.completed_tricks().unwrap().len();
+            game.update(transition).unwrap();
+            if let Transition::Play { player, card } = transition {
+                played.push((player, card));
+            }
+            let tricks = game.completed_tricks().unwrap_or_default();
+            if played.len() < 3 {
+                assert_eq!(tricks.len(), before);
+            } else if !tricks.is_empty() {
+                // the round goes on after this trick
+                assert_eq!(tricks.len(), before + 1);
+                let trick = tricks.last().unwrap();
+                assert_eq!(Some(trick.leader), leader);
+                assert_eq!(trick.cards, played);
+                assert_eq!(game.leader(), Some(trick.winner));
+                played.clear();
+            } else {
+                played.clear();
+            }
+        }
+        assert_eq!(game.completed_tricks(), None);
+    }
+
+    #[test]
+    fn test_rewind() {
+        let mut driver = RandomDriver::new(5);
+        let mut game = Judgment::new(3, 2, None);
+        assert_eq!(game.undo(), Err(InvalidRewind::NothingToUndo));
+        game.start().unwrap();
+        assert_eq!(game.undo(), Err(InvalidRewind::NothingToUndo));
+        let mut snapshots = vec![game.clone()];
+        while !game.is_over() {
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+            snapshots.push(game.clone());
+        }
+        assert_eq!(
+            game.rewind(snapshots.len()),
+            Err(InvalidRewind::BeyondHistory {
+                requested: snapshots.len(),
+                available: snapshots.len() - 1
+            })
+        );
+        for (index, snapshot) in snapshots.iter().enumerate().rev() {
+            game.rewind(index).unwrap();
+            assert_eq!(game.stage, snapshot.stage);
+            assert_eq!(game.players, snapshot.players);
+            assert_eq!(game.scores, snapshot.scores);
+            assert_eq!(game.history.len(), index);
+        }
+    }
+
+    #[test]
+    fn test_configured_rounds_and_trumps() {
+        let mut driver = RandomDriver::new(13);
+        let config = GameConfig::builder(3, 3)
+            .hand_sizes(HandSizeSchedule::UpAndDown)
+            .trump_rotation(vec![None, Some(Suit::Clubs)])
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        let mut rounds = Vec::new();
+        while !game.is_over() {
+            let transition = driver.next_transition(&game).unwrap();
+            if matches!(transition, Transition::Deal { .. }) {
+                rounds.push((game.hand_size().unwrap(), game.trump_suit().cloned()));
+            }
+            game.update(transition).unwrap();
+        }
+        assert_eq!(
+            rounds,
+            [
+                (3, None),
+                (2, Some(Suit::Clubs)),
+                (1, None),
+                (2, Some(Suit::Clubs)),
+                (3, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_player_restriction_can_be_disabled() {
+        for (restricted, expected) in [(true, false), (false, true)] {
+            let config = GameConfig::builder(2, 1)
+                .last_player_restriction(restricted)
+                .build()
+                .unwrap();
+            let mut game = Judgment::with_config(config);
+            game.start().unwrap();
+            game.update(Transition::Deal { seed: [0; 32] }).unwrap();
+            game.update(Transition::PredictScore {
+                player: 0,
+                score: 0,
+            })
+            .unwrap();
+            let last = game.update(Transition::PredictScore {
+                player: 1,
+                score: 1,
+            });
+            assert_eq!(last.is_ok(), expected);
+        }
+    }
+
+    #[test]
+    fn test_legal_moves_agree_with_update() {
+        let mut driver = RandomDriver::new(17);
+        let mut game = Judgment::new(4, 6, None);
+        assert_eq!(game.phase(), Phase::NotStarted);
+        game.start().unwrap();
+        while !game.is_over() {
+            let current = game.current_player();
+            for player in 0..4 {
+                let legal_cards = game.legal_cards(player);
+                let legal_predictions = game.legal_predictions(player);
+                if current != Some(player) {
+                    assert!(legal_cards.is_empty());
+                    assert!(legal_predictions.is_empty());
+                    continue;
+                }
+                match game.phase() {
+                    Phase::Predicting => {
+                        assert!(legal_cards.is_empty());
+                        for score in 0..=game.hand_size().unwrap() + 1 {
+                            let accepted = game
+                                .clone()
+                                .update(Transition::PredictScore { player, score })
+                                .is_ok();
+                            assert_eq!(accepted, legal_predictions.contains(&score));
+                        }
+                    }
+                    Phase::Playing => {
+                        assert!(legal_predictions.is_empty());
+                        for card in game.hand_of_player(player).unwrap() {
+                            let accepted = game
+                                .clone()
+                                .update(Transition::Play {
+                                    player,
+                                    card: *card,
+                                })
+                                .is_ok();
+                            assert_eq!(accepted, legal_cards.contains(card));
+                        }
+                    }
+                    phase => panic!("no player should have a turn in {phase:?}"),
+                }
+            }
+            if game.phase() == Phase::Dealing {
+                assert_eq!(current, None);
+            }
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        assert_eq!(game.phase(), Phase::Over);
+        assert_eq!(game.current_player(), None);
+    }
+
+    #[test]
+    fn test_replay_midway_matches_hands() {
+        let mut driver = RandomDriver::new(11);
+        let mut game = Judgment::new(3, 4, Some(1));
+        game.start().unwrap();
+        for _ in 0..10 {
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        let replayed = Judgment::replay(game.config.clone(), game.history()).unwrap();
+        assert_eq!(replayed.stage, game.stage);
+        assert_eq!(replayed.players, game.players);
+        assert_eq!(replayed.trick, game.trick);
+        assert_eq!(replayed.first_of_trick, game.first_of_trick);
+    }
+
+    #[test]
+    fn test_update_event_order() {
+        let mut driver = RandomDriver::new(19);
+        let mut game = Judgment::new(3, 2, None);
+        game.start().unwrap();
+        let mut rounds_ended = 0;
+        let mut tricks_won = 0;
+        while !game.is_over() {
+            let transition = driver.next_transition(&game).unwrap();
+            let updates = game.update(transition).unwrap();
+            // every variant name in order, to compare against the documented order
+            let kinds: Vec<&str> = updates
+                .iter()
+                .map(|update| match update {
+                    StateUpdate::Trick(_) => "Trick",
+                    StateUpdate::Predictions(_) => "Predictions",
+                    StateUpdate::RoundScores(_) => "RoundScores",
+                    StateUpdate::GameScores(_) => "GameScores",
+                    StateUpdate::TeamScores(_) => "TeamScores",
+                    StateUpdate::PredictionSealed { .. } => "PredictionSealed",
+                    StateUpdate::CardsDealt => "CardsDealt",
+                    StateUpdate::TrickWon { .. } => "TrickWon",
+                    StateUpdate::RoundEnded { .. } => "RoundEnded",
+                    StateUpdate::RoundStarted { .. } => "RoundStarted",
+                    StateUpdate::TrumpTurnedUp(_) => "TrumpTurnedUp",
+                    StateUpdate::TurnChanged(_) => "TurnChanged",
+                    StateUpdate::GameOver { .. } => "GameOver",
+                    StateUpdate::ClaimMade { .. } => "ClaimMade",
+                    StateUpdate::ClaimAccepted { .. } => "ClaimAccepted",
+                    StateUpdate::ClaimRejected { .. } => "ClaimRejected",
+                })
+                .collect();
+            let expected: &[&str] = match transition {
+                Transition::Deal { .. } => &["RoundStarted", "CardsDealt", "TurnChanged"],
+                Transition::PredictScore { .. } => &["Predictions", "TurnChanged"],
+                Transition::PredictBlind { .. } => &["Predictions"],
+                Transition::Play { .. } if game.is_over() => &[
+                    "Trick",
+                    "TrickWon",
+                    "RoundScores",
+                    "RoundEnded",
+                    "GameScores",
+                    "GameOver",
+                ],
+                Transition::Play { .. } if game.phase() == Phase::Dealing => &[
+                    "Trick",
+                    "TrickWon",
+                    "RoundScores",
+                    "RoundEnded",
+                    "GameScores",
+                ],
+                Transition::Play { .. } if updates.len() == 4 => {
+                    &["Trick", "TrickWon", "RoundScores", "TurnChanged"]
+                }
+                Transition::Play { .. } => &["Trick", "TurnChanged"],
+                Transition::Claim { .. }
+                | Transition::AcceptClaim { .. }
+                | Transition::RejectClaim { .. } => unreachable!("the driver never claims"),
+            };
+            assert_eq!(kinds, expected);
+            for update in updates {
+                match update {
+                    StateUpdate::TurnChanged(player) => {
+                        assert_eq!(Some(player), game.current_player());
+                    }
+                    StateUpdate::TrickWon { winner, cards } => {
+                        tricks_won += 1;
+                        assert!(cards.iter().all(|card| card.is_some()));
+                        assert_eq!(
+                            game.current_player(),
+                            Some(winner).filter(|_| { game.phase() == Phase::Playing })
+                        );
+                    }
+                    StateUpdate::RoundEnded {
+                        predictions,
+                        tricks,
+                        deltas,
+                        ..
+                    } => {
+                        rounds_ended += 1;
+                        assert_eq!(predictions.len(), 3);
+                        assert_eq!(deltas.len(), 3);
+                        assert_eq!(tricks.iter().sum::<u8>(), 3 - rounds_ended);
+                    }
+                    StateUpdate::GameOver {
+                        final_scores,
+                        winners,
+                    } => {
+                        assert_eq!(final_scores, game.scores());
+                        let best = final_scores.iter().max().unwrap();
+                        assert!(winners.iter().all(|winner| final_scores[*winner] == *best));
+                    }
+                    _ => (),
+                }
+            }
+        }
+        assert_eq!(rounds_ended, 2);
+        assert_eq!(tricks_won, 3);
+    }
+
+    /// A random but valid game config, drawn from `rng`.
+    fn random_config<R: Rng>(rng: &mut R) -> GameConfig {
+        let players: u8 = rng.gen_range(1..=8);
+        let max_hand_size: u8 = rng.gen_range(1..=13);
+        let schedules = [
+            HandSizeSchedule::Descending,
+            HandSizeSchedule::Ascending,
+            HandSizeSchedule::UpAndDown,
+        ];
+        let scorings = [
+            Scoring::Standard,
+            Scoring::TenPlusBid,
+            Scoring::PenaltyPerTrick {
+                hit_bonus: 10,
+                penalty: 2,
+            },
+            Scoring::ZeroBidBonus { bonus: 5 },
+        ];
+        let duplicates = [
+            DuplicateCardRule::FirstWins,
+            DuplicateCardRule::LastWins,
+            DuplicateCardRule::Cancel,
+        ];
+        let selections = [TrumpSelection::Rotation, TrumpSelection::TurnUp];
+        let biddings = [BiddingMode::Sequential, BiddingMode::Sealed];
+        let jokers = rng.gen_range(0..=2);
+        let required_decks = (players * max_hand_size).div_ceil(52 + jokers);
+        let mut builder = GameConfig::builder(players, max_hand_size);
+        if rng.gen() {
+            builder = builder.blind_predictions(rng.gen_range(1..=3));
+        }
+        if players > 1 && rng.gen() {
+            let teams = rng.gen_range(2..=players);
+            let bid = [TeamBid::Sum, TeamBid::Joint][rng.gen_range(0..2)];
+            let seats = (0..usize::from(players))
+                .map(|seat| seat % usize::from(teams))
+                .collect();
+            builder = builder.teams(Teams::new(seats, bid));
+        }
+        builder
+            .decks(required_decks + rng.gen_range(0..=2))
+            .jokers(jokers)
+            .hand_sizes(schedules[rng.gen_range(0..schedules.len())])
+            .last_player_restriction(rng.gen())
+            .scoring(scorings[rng.gen_range(0..scorings.len())].clone())
+            .duplicate_cards(duplicates[rng.gen_range(0..duplicates.len())])
+            .trump_selection(selections[rng.gen_range(0..selections.len())])
+            .bidding(biddings[rng.gen_range(0..biddings.len())])
+            .build()
+            .unwrap()
+    }
+
+    /// Check the invariants that must hold between any two transitions.
+    fn check_invariants(game: &Judgment) {
+        let (Stage::PredictScores(round) | Stage::Play(round)) = &game.stage else {
+            return;
+        };
+        let players = game.players.len();
+        // sealed predictions are only revealed once all of them are in
+        if let Stage::PredictScores(round) = &game.stage {
+            let sealed = round.sealed_scores.iter().flatten().count();
+            match game.config.bidding() {
+                BiddingMode::Sequential => assert_eq!(sealed, 0),
+                BiddingMode::Sealed => {
+                    assert!(round.predicted_scores.iter().all(Option::is_none));
+                    assert!(sealed < players);
+                }
+            }
+        } else {
+            assert!(round.sealed_scores.iter().all(Option::is_none));
+            assert!(round.predicted_scores.iter().all(Option::is_some));
+        }
+        // a joint prediction holds for every member of the team
+        if let Some(teams) = game
+            .config
+            .teams()
+            .filter(|teams| teams.bid() == TeamBid::Joint)
+        {
+            for scores in [&round.predicted_scores, &round.sealed_scores] {
+                for seat in 0..players {
+                    let first = teams.members(teams.team_of(seat)).next().unwrap();
+                    assert_eq!(scores[seat], scores[first]);
+                }
+            }
+        }
+        // conservation of cards: everything dealt is either in a hand, on the
+        // table or in a completed trick
+        let cards = game
+            .players
+            .iter()
+            .map(|player| player.hand().len())
+            .sum::<usize>()
+            + game.trick.iter().flatten().count()
+            + round.completed_tricks.len() * players;
+        assert_eq!(cards, usize::from(round.hand_size) * players);
+        if round.completed_tricks.is_empty() && game.trick.iter().all(Option::is_none) {
+            // no card is dealt more often than there are decks, only worth
+            // checking right after the deal since no cards enter later
+            let dealt: Vec<&Card> = game
+                .players
+                .iter()
+                .flat_map(|player| player.hand())
+                .chain(&round.turned_up)
+                .collect();
+            for card in &dealt {
+                let copies = dealt.iter().filter(|other| *other == card).count();
+                let per_deck = if card.is_joker() {
+                    game.config.jokers()
+                } else {
+                    1
+                };
+                assert!(copies <= usize::from(game.config.decks() * per_deck));
+            }
+        }
+        // every completed trick was won by exactly one player
+        assert_eq!(
+            round
+                .trick_scores
+                .iter()
+                .map(|tricks| usize::from(*tricks))
+                .sum::<usize>(),
+            round.completed_tricks.len()
+        );
+        // earlier tricks were already checked after the transition that
+        // completed them
+        if let Some(trick) = round.completed_tricks.last() {
+            assert_eq!(trick.cards.len(), players);
+            let winning_card = trick.cards.iter().find(|(seat, _)| *seat == trick.winner);
+            let winning_card = winning_card.unwrap().1;
+            let duplicates = game.config.duplicate_cards();
+            for (_, card) in &trick.cards {
+                // an identical card may have won instead, or cancelled it
+                if *card == winning_card || duplicates != DuplicateCardRule::FirstWins {
+                    continue;
+                }
+                assert!(
+                    trick_card_comparator(&winning_card, card, round.trump_suit.as_ref()).is_ge()
+                );
+            }
+            assert_eq!(
+                trick_winner(&trick.cards, round.trump_suit.as_ref(), duplicates),
+                Some(trick.winner)
+            );
+        }
+    }
+
+    #[test]
+    fn test_dealer_rotation() {
+        let mut driver = RandomDriver::new(29);
+        let mut game = Judgment::new(3, 7, None);
+        assert_eq!(game.dealer(), None);
+        game.start().unwrap();
+        let mut rounds = 0;
+        while !game.is_over() {
+            let dealer = (rounds + 2) % 3;
+            let opener = (dealer + 1) % 3;
+            assert_eq!(game.phase(), Phase::Dealing);
+            assert_eq!(game.dealer(), Some(dealer));
+            let updates = game.update(driver.next_transition(&game).unwrap()).unwrap();
+            assert!(matches!(
+                updates[0],
+                StateUpdate::RoundStarted { starting_player, .. } if starting_player == opener
+            ));
+            // bidding goes around the table starting left of the dealer
+            for offset in 0..3 {
+                assert_eq!(game.current_player(), Some((opener + offset) % 3));
+                game.update(driver.next_transition(&game).unwrap()).unwrap();
+            }
+            // and so does the first trick
+            assert_eq!(game.leader(), Some(opener));
+            assert_eq!(game.current_player(), Some(opener));
+            while game.phase() == Phase::Playing {
+                assert_eq!(game.dealer(), Some(dealer));
+                let updates = game.update(driver.next_transition(&game).unwrap()).unwrap();
+                // every other trick is led by the winner of the previous one
+                for update in updates {
+                    if let (StateUpdate::TrickWon { winner, .. }, Phase::Playing) =
+                        (update, game.phase())
+                    {
+                        assert_eq!(game.leader(), Some(winner));
+                        assert_eq!(game.current_player(), Some(winner));
+                    }
+                }
+            }
+            assert!(matches!(game.phase(), Phase::Dealing | Phase::Over));
+            rounds += 1;
+        }
+        assert_eq!(rounds, 7);
+        assert_eq!(game.dealer(), None);
+        assert_eq!(game.leader(), None);
+    }
+
+    /// Assert that `transition` is rejected with `expected` and that the game
+    /// is exactly as it was before.
+    fn assert_rejected(game: &mut Judgment, transition: Transition, expected: InvalidTransition) {
+        let before = game.clone();
+        assert_eq!(game.validate(&transition), Err(expected));
+        assert_eq!(game.update(transition).unwrap_err(), expected);
+        assert_eq!(game.stage, before.stage);
+        assert_eq!(game.players, before.players);
+        assert_eq!(game.trick, before.trick);
+        assert_eq!(game.first_of_trick, before.first_of_trick);
+        assert_eq!(game.scores, before.scores);
+        assert_eq!(game.history, before.history);
+    }
+
+    #[test]
+    fn test_every_invalid_transition_leaves_game_untouched() {
+        let deal = Transition::Deal { seed: [6; 32] };
+        let predict = |player, score| Transition::PredictScore { player, score };
+        let play = |player, card| Transition::Play { player, card };
+        let blind = |player, score| Transition::PredictBlind { player, score };
+        let some_card = Card::new_normal(Suit::Spades, Rank::Ace);
+        let mut game = Judgment::new(2, 2, None);
+
+        // not started yet
+        assert_rejected(&mut game, deal, InvalidTransition::DealBeforeGameStart);
+        assert_rejected(
+            &mut game,
+            predict(0, 0),
+            InvalidTransition::PredictBeforeDeal,
+        );
+        assert_rejected(
+            &mut game,
+            play(0, some_card),
+            InvalidTransition::PlayBeforeScorePrediction,
+        );
+        assert_rejected(&mut game, blind(0, 0), InvalidTransition::PredictBeforeDeal);
+
+        // waiting for the deal
+        game.start().unwrap();
+        assert_eq!(game.start(), Err(InvalidTransition::Restart));
+        assert_rejected(
+            &mut game,
+            predict(0, 0),
+            InvalidTransition::PredictBeforeDeal,
+        );
+        assert_rejected(
+            &mut game,
+            play(0, some_card),
+            InvalidTransition::PlayBeforeScorePrediction,
+        );
+
+        // predicting
+        game.update(deal).unwrap();
+        let first = game.current_player().unwrap();
+        let second = 1 - first;
+        assert_rejected(&mut game, deal, InvalidTransition::ReDeal);
+        assert_rejected(
+            &mut game,
+            play(first, some_card),
+            InvalidTransition::PlayBeforeScorePrediction,
+        );
+        assert_rejected(
+            &mut game,
+            predict(second, 0),
+            InvalidTransition::OutOfTurnPlay,
+        );
+        assert_rejected(&mut game, predict(5, 0), InvalidTransition::OutOfTurnPlay);
+        assert_rejected(
+            &mut game,
+            predict(first, 3),
+            InvalidTransition::PredictionOutOfRange,
+        );
+        game.update(predict(first, 1)).unwrap();
+        assert_rejected(
+            &mut game,
+            predict(second, 1),
+            InvalidTransition::LastPlayerPrediction,
+        );
+        game.update(predict(second, 0)).unwrap();
+
+        // playing
+        assert_rejected(&mut game, deal, InvalidTransition::ReDeal);
+        assert_rejected(&mut game, predict(first, 0), InvalidTransition::RePredict);
+        let leader = game.current_player().unwrap();
+        let follower = 1 - leader;
+        let follower_card = game.hand_of_player(follower).unwrap()[0];
+        assert_rejected(
+            &mut game,
+            play(follower, follower_card),
+            InvalidTransition::OutOfTurnPlay,
+        );
+        let missing_card = follower_card;
+        assert!(!game.hand_of_player(leader).unwrap().contains(&missing_card));
+        assert_rejected(
+            &mut game,
+            play(leader, missing_card),
+            InvalidTransition::NoSuchPlayerCard,
+        );
+        // the rejected card must not have become the led card
+        assert!(game.first_of_trick.is_none());
+        assert_eq!(game.current_player(), Some(leader));
+
+        // find a deal in which the follower could break suit illegally
+        let suit_mismatch = (0..=u8::MAX).find_map(|seed| {
+            let mut game = Judgment::new(2, 2, None);
+            game.start().unwrap();
+            game.update(Transition::Deal { seed: [seed; 32] }).unwrap();
+            let first = game.current_player().unwrap();
+            game.update(predict(first, 1)).unwrap();
+            game.update(predict(1 - first, 0)).unwrap();
+            let leader = game.current_player().unwrap();
+            let led = game.hand_of_player(leader).unwrap()[0];
+            game.update(play(leader, led)).unwrap();
+            let hand = game.hand_of_player(1 - leader).unwrap();
+            let has_suit = hand.iter().any(|card| card.suit() == led.suit());
+            let off_suit = hand.iter().find(|card| card.suit() != led.suit());
+            match (has_suit, off_suit) {
+                (true, Some(card)) => Some((game.clone(), play(1 - leader, *card))),
+                _ => None,
+            }
+        });
+        let (mut game, transition) = suit_mismatch.expect("some deal splits the suits");
+        assert_rejected(&mut game, transition, InvalidTransition::SuitMismatch);
+
+        // over
+        let mut game = Judgment::new(2, 1, None);
+        game.start().unwrap();
+        RandomDriver::new(0).play_out(&mut game);
+        assert_rejected(&mut game, deal, InvalidTransition::GameOver);
+        assert_rejected(&mut game, predict(0, 0), InvalidTransition::GameOver);
+        assert_rejected(&mut game, play(0, some_card), InvalidTransition::GameOver);
+        assert_rejected(&mut game, blind(0, 0), InvalidTransition::GameOver);
+    }
+
+    #[test]
+    fn test_random_games_keep_invariants() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(2024);
+        for game_seed in 0..2000 {
+            let config = random_config(&mut rng);
+            let mut game = Judgment::with_config(config.clone());
+            let mut driver = RandomDriver::new(game_seed);
+            game.start().unwrap();
+            let mut scores = vec![0; usize::from(config.players())];
+            let mut team_scores = vec![0; config.teams().map_or(0, Teams::count)];
+            let mut rounds = 0;
+            while let Some(transition) = driver.next_transition(&game) {
+                // joint predictions are checked once the round is over
+                let joint = config
+                    .teams()
+                    .is_some_and(|teams| teams.bid() == TeamBid::Joint);
+                if let (
+                    Stage::PredictScores(round),
+                    Transition::PredictScore { player, .. },
+                    false,
+                ) = (&game.stage, transition, joint)
+                {
+                    let predicted = round.predicted_scores.iter().flatten().sum::<u8>();
+                    let last = round
+                        .predicted_scores
+                        .iter()
+                        .filter(|p| p.is_none())
+                        .count()
+                        == 1;
+                    let completing = round.hand_size.checked_sub(predicted);
+                    let legal = game.legal_predictions(player);
+                    let allowed = completing.is_some_and(|score| legal.contains(&score));
+                    assert!(
+                        !(last && config.last_player_restriction() && allowed),
+                        "game {game_seed}: the last player could complete the hand size"
+                    );
+                }
+                let updates = game
+                    .update(transition)
+                    .unwrap_or_else(|err| panic!("game {game_seed}: {err}"));
+                check_invariants(&game);
+                for update in updates {
+                    if let StateUpdate::RoundEnded {
+                        predictions,
+                        tricks,
+                        deltas,
+                        blind,
+                    } = update
+                    {
+                        let hand_size = config.hand_sizes()[rounds];
+                        assert_eq!(tricks.iter().sum::<u8>(), hand_size, "game {game_seed}");
+                        let team_predictions = match config.teams() {
+                            Some(teams) => teams.predictions(&predictions),
+                            None => predictions.clone(),
+                        };
+                        if config.last_player_restriction() {
+                            assert_ne!(team_predictions.iter().sum::<u8>(), hand_size);
+                        }
+                        let multiplier = i64::from(config.blind_multiplier().unwrap_or(1));
+                        let result = game.round_results.last().unwrap();
+                        match config.teams() {
+                            Some(teams) if teams.bid() == TeamBid::Joint => {
+                                for (seat, delta) in deltas.iter().enumerate() {
+                                    assert_eq!(*delta, result.team_deltas[teams.team_of(seat)]);
+                                }
+                            }
+                            _ => {
+                                let settled = config.scoring().settle(&predictions, &tricks);
+                                for ((delta, settled), blind) in
+                                    deltas.iter().zip(settled).zip(&blind)
+                                {
+                                    let expected = if *blind {
+                                        settled * multiplier
+                                    } else {
+                                        settled
+                                    };
+                                    assert_eq!(*delta, expected, "game {game_seed}");
+                                }
+                            }
+                        }
+                        if let Some(teams) = config.teams() {
+                            let settled = config
+                                .scoring()
+                                .settle(&team_predictions, &teams.tricks(&tricks));
+                            for (team, settled) in settled.into_iter().enumerate() {
+                                let all_blind = teams.members(team).all(|seat| blind[seat]);
+                                let expected = if all_blind {
+                                    settled * multiplier
+                                } else {
+                                    settled
+                                };
+                                assert_eq!(result.team_deltas[team], expected, "game {game_seed}");
+                                team_scores[team] += expected;
+                            }
+                            assert_eq!(game.team_scores(), Some(&team_scores[..]));
+                        }
+                        for (score, delta) in scores.iter_mut().zip(deltas) {
+                            *score += delta;
+                        }
+                        assert_eq!(game.scores(), scores, "game {game_seed}");
+                        rounds += 1;
+                    }
+                }
+            }
+            assert!(game.is_over(), "game {game_seed} got stuck");
+            assert_eq!(rounds, config.rounds(), "game {game_seed}");
+        }
+    }
+
+    #[test]
+    fn test_turn_up_trump() {
+        let config = GameConfig::builder(3, 5)
+            .trump_selection(TrumpSelection::TurnUp)
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config.clone());
+        game.start().unwrap();
+        assert_eq!(game.trump_suit(), None);
+        let updates = game.update(Transition::Deal { seed: [3; 32] }).unwrap();
+        let turned_up = *game.turned_up_card().unwrap();
+        assert_eq!(game.trump_suit(), turned_up.suit());
+        assert_eq!(
+            updates,
+            [
+                StateUpdate::RoundStarted {
+                    hand_size: 5,
+                    trump: turned_up.suit().copied(),
+                    starting_player: 0,
+                },
+                StateUpdate::CardsDealt,
+                StateUpdate::TrumpTurnedUp(Some(turned_up)),
+                StateUpdate::TurnChanged(0),
+            ]
+        );
+        // with a single deck, the turned card is in nobody's hand
+        for player in 0..3 {
+            assert!(!game.hand_of_player(player).unwrap().contains(&turned_up));
+            assert_eq!(game.view_for(player).unwrap().turned_up, Some(turned_up));
+        }
+        // the same seed turns up the same card
+        let replayed = Judgment::replay(config, game.history()).unwrap();
+        assert_eq!(replayed.turned_up_card(), Some(&turned_up));
+
+        // with every card dealt, there is nothing to turn up and no trump
+        let config = GameConfig::builder(4, 13)
+            .trump_selection(TrumpSelection::TurnUp)
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        let updates = game.update(Transition::Deal { seed: [3; 32] }).unwrap();
+        assert!(updates.contains(&StateUpdate::TrumpTurnedUp(None)));
+        assert_eq!(game.turned_up_card(), None);
+        assert_eq!(game.trump_suit(), None);
+    }
+
+    #[test]
+    fn test_jokers() {
+        let joker = Card::new_joker();
+        let ace = Card::new_normal(Suit::Spades, Rank::Ace);
+        let two = Card::new_normal(Suit::Clubs, Rank::Numeric(2));
+        assert_eq!(card_comparator(&joker, &ace), Ordering::Greater);
+        assert_eq!(card_comparator(&two, &joker), Ordering::Less);
+        assert_eq!(card_comparator(&joker, &joker), Ordering::Equal);
+        let spades = Some(&Suit::Spades);
+        assert_eq!(
+            trick_card_comparator(&joker, &ace, spades),
+            Ordering::Greater
+        );
+        assert_eq!(trick_card_comparator(&ace, &joker, spades), Ordering::Less);
+        assert_eq!(
+            trick_card_comparator(&joker, &joker, spades),
+            Ordering::Equal
+        );
+
+        let config = GameConfig::builder(3, 2).jokers(1).build().unwrap();
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [0; 32] }).unwrap();
+        for player in 0..3 {
+            game.update(Transition::PredictScore { player, score: 0 })
+                .unwrap();
+        }
+        let heart = |rank| Card::new_normal(Suit::Hearts, rank);
+        game.players[0].assign([joker, two].into_iter());
+        game.players[1].assign([heart(Rank::Numeric(5)), ace].into_iter());
+        game.players[2].assign([heart(Rank::King), ace].into_iter());
+        // a led joker sets no suit, the first card after it does
+        game.update(Transition::Play {
+            player: 0,
+            card: joker,
+        })
+        .unwrap();
+        assert_eq!(game.legal_cards(1).len(), 2);
+        game.update(Transition::Play {
+            player: 1,
+            card: heart(Rank::Numeric(5)),
+        })
+        .unwrap();
+        assert_eq!(game.legal_cards(2), [heart(Rank::King)]);
+        let updates = game
+            .update(Transition::Play {
+                player: 2,
+                card: heart(Rank::King),
+            })
+            .unwrap();
+        assert!(matches!(
+            updates[1],
+            StateUpdate::TrickWon { winner: 0, .. }
+        ));
+        // a joker may be played instead of following suit
+        let mut game = game.clone();
+        game.players[0].assign([joker, two].into_iter());
+        game.update(Transition::Play {
+            player: 0,
+            card: two,
+        })
+        .unwrap();
+        game.players[1].assign([joker, Card::new_normal(Suit::Clubs, Rank::Ace)].into_iter());
+        assert_eq!(game.legal_cards(1).len(), 2);
+    }
+
+    #[test]
+    fn test_sealed_predictions() {
+        let config = GameConfig::builder(3, 4)
+            .bidding(BiddingMode::Sealed)
+            .blind_predictions(3)
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        // a blind prediction is sealed as well
+        let updates = game
+            .update(Transition::PredictBlind {
+                player: 2,
+                score: 3,
+            })
+            .unwrap();
+        assert_eq!(updates, [StateUpdate::PredictionSealed { player: 2 }]);
+        assert_eq!(game.predicted_scores(), Some(&[None, None, None][..]));
+        game.update(Transition::Deal { seed: [8; 32] }).unwrap();
+        assert_eq!(game.current_player(), Some(0));
+        assert!(game.legal_predictions(2).is_empty());
+        // anyone who has not predicted may do so, in any order
+        let updates = game
+            .update(Transition::PredictScore {
+                player: 1,
+                score: 0,
+            })
+            .unwrap();
+        assert_eq!(
+            updates,
+            [
+                StateUpdate::PredictionSealed { player: 1 },
+                StateUpdate::TurnChanged(0)
+            ]
+        );
+        assert_rejected(
+            &mut game,
+            Transition::PredictScore {
+                player: 1,
+                score: 1,
+            },
+            InvalidTransition::RePredict,
+        );
+        assert_eq!(game.predicted_scores(), Some(&[None, None, None][..]));
+        let view = game.view_for(0).unwrap();
+        assert_eq!(view.predictions, [None, None, None]);
+        assert_eq!(view.predicted, [false, true, true]);
+        assert_eq!(view.sealed_prediction, None);
+        assert_eq!(view.blind, [false, false, true]);
+        assert_eq!(game.view_for(1).unwrap().sealed_prediction, Some(0));
+        assert_eq!(game.view_for(2).unwrap().sealed_prediction, Some(3));
+        // nobody is the last to predict, so the predictions may add up
+        assert_eq!(game.legal_predictions(0), [0, 1, 2, 3, 4]);
+        let updates = game
+            .update(Transition::PredictScore {
+                player: 0,
+                score: 1,
+            })
+            .unwrap();
+        let revealed = vec![Some(1), Some(0), Some(3)];
+        assert_eq!(
+            updates,
+            [
+                StateUpdate::PredictionSealed { player: 0 },
+                StateUpdate::Predictions(revealed.clone()),
+                StateUpdate::TurnChanged(0),
+            ]
+        );
+        assert_eq!(game.phase(), Phase::Playing);
+        assert_eq!(game.predicted_scores(), Some(&revealed[..]));
+        let view = game.view_for(1).unwrap();
+        assert_eq!(view.predictions, revealed);
+        assert_eq!(view.sealed_prediction, None);
+    }
+
+    #[test]
+    fn test_blind_predictions() {
+        let blind = |player, score| Transition::PredictBlind { player, score };
+        let mut game = Judgment::new(2, 3, None);
+        game.start().unwrap();
+        assert_rejected(
+            &mut game,
+            blind(0, 1),
+            InvalidTransition::BlindPredictionNotAllowed,
+        );
+
+        let config = GameConfig::builder(2, 3)
+            .blind_predictions(2)
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config.clone());
+        game.start().unwrap();
+        assert_eq!(game.legal_blind_predictions(1), [0, 1, 2, 3]);
+        let updates = game.update(blind(1, 3)).unwrap();
+        assert_eq!(updates, [StateUpdate::Predictions(vec![None, Some(3)])]);
+        assert_eq!(game.predicted_scores(), Some(&[None, Some(3)][..]));
+        assert_rejected(&mut game, blind(1, 2), InvalidTransition::RePredict);
+        assert_rejected(&mut game, blind(2, 2), InvalidTransition::OutOfTurnPlay);
+        assert_rejected(
+            &mut game,
+            blind(0, 4),
+            InvalidTransition::PredictionOutOfRange,
+        );
+        // the last player to predict may be predicting blind
+        assert_eq!(game.legal_blind_predictions(0), [1, 2, 3]);
+        assert_rejected(
+            &mut game,
+            blind(0, 0),
+            InvalidTransition::LastPlayerPrediction,
+        );
+
+        // blind predictors are skipped once the cards are dealt
+        game.update(Transition::Deal { seed: [4; 32] }).unwrap();
+        assert_rejected(
+            &mut game,
+            blind(0, 1),
+            InvalidTransition::BlindPredictionAfterDeal,
+        );
+        assert_eq!(game.current_player(), Some(0));
+        assert!(game.legal_predictions(1).is_empty());
+        game.update(Transition::PredictScore {
+            player: 0,
+            score: 1,
+        })
+        .unwrap();
+        assert_eq!(game.phase(), Phase::Playing);
+        assert_eq!(game.current_player(), Some(0));
+
+        let ended = loop {
+            let player = game.current_player().unwrap();
+            let card = game.legal_cards(player)[0];
+            let updates = game.update(Transition::Play { player, card }).unwrap();
+            if let Some(ended) = updates
+                .into_iter()
+                .find(|update| matches!(update, StateUpdate::RoundEnded { .. }))
+            {
+                break ended;
+            }
+        };
+        let StateUpdate::RoundEnded {
+            predictions,
+            tricks,
+            deltas,
+            blind,
+        } = ended
+        else {
+            unreachable!();
+        };
+        assert_eq!(blind, [false, true]);
+        let settled = config.scoring().settle(&predictions, &tricks);
+        assert_eq!(deltas, [settled[0], settled[1] * 2]);
+        assert_eq!(game.scores(), deltas);
+        let replayed = Judgment::replay(config, game.history()).unwrap();
+        assert_eq!(replayed.scores(), game.scores());
+    }
 
-    use crate::trick_card_comparator;
+    #[test]
+    fn test_teams() {
+        // partners sit opposite each other and predictions add up
+        let config = GameConfig::builder(4, 2)
+            .teams(Teams::new(vec![0, 1, 0, 1], TeamBid::Sum))
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config.clone());
+        game.start().unwrap();
+        assert_eq!(game.team_scores(), Some(&[0, 0][..]));
+        game.update(Transition::Deal { seed: [5; 32] }).unwrap();
+        for (player, score) in [(0, 1), (1, 0), (2, 0), (3, 0)] {
+            game.update(Transition::PredictScore { player, score })
+                .unwrap();
+        }
+        let mut updates = Vec::new();
+        while game.phase() == Phase::Playing {
+            let player = game.current_player().unwrap();
+            let card = game.legal_cards(player)[0];
+            updates = game.update(Transition::Play { player, card }).unwrap();
+        }
+        let result = &game.round_results[0];
+        let team_tricks = [
+            result.tricks[0] + result.tricks[2],
+            result.tricks[1] + result.tricks[3],
+        ];
+        let team_deltas = config.scoring().settle(&[1, 0], &team_tricks);
+        assert_eq!(result.team_deltas, team_deltas);
+        assert_eq!(game.team_scores(), Some(&team_deltas[..]));
+        assert_eq!(
+            result.deltas,
+            config.scoring().settle(&result.predictions, &result.tricks)
+        );
+        assert!(updates.contains(&StateUpdate::TeamScores(team_deltas.clone())));
+        assert_eq!(game.view_for(1).unwrap().team_scores, team_deltas);
+
+        // a joint prediction is made once for the whole team
+        let config = GameConfig::builder(4, 2)
+            .teams(Teams::new(vec![0, 1, 0, 1], TeamBid::Joint))
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config.clone());
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [5; 32] }).unwrap();
+        let updates = game
+            .update(Transition::PredictScore {
+                player: 0,
+                score: 1,
+            })
+            .unwrap();
+        assert_eq!(
+            updates,
+            [
+                StateUpdate::Predictions(vec![Some(1), None, Some(1), None]),
+                StateUpdate::TurnChanged(1),
+            ]
+        );
+        // the second team is the last to predict
+        assert_eq!(game.legal_predictions(1), [0, 2]);
+        game.update(Transition::PredictScore {
+            player: 1,
+            score: 2,
+        })
+        .unwrap();
+        assert_eq!(game.phase(), Phase::Playing);
+        assert_eq!(game.current_player(), Some(0));
+        while game.phase() == Phase::Playing {
+            let player = game.current_player().unwrap();
+            game.update(Transition::Play {
+                player,
+                card: game.legal_cards(player)[0],
+            })
+            .unwrap();
+        }
+        let result = &game.round_results[0];
+        assert_eq!(result.predictions, [1, 2, 1, 2]);
+        let team_tricks = Teams::new(vec![0, 1, 0, 1], TeamBid::Joint).tricks(&result.tricks);
+        let team_deltas = config.scoring().settle(&[1, 2], &team_tricks);
+        assert_eq!(result.team_deltas, team_deltas);
+        let seat_deltas: Vec<i64> = (0..4).map(|seat| team_deltas[seat % 2]).collect();
+        assert_eq!(result.deltas, seat_deltas);
+
+        RandomDriver::new(1).play_out(&mut game);
+        let result = game.result().unwrap();
+        let best = result.team_scores.iter().max().unwrap();
+        let winners = result.winners();
+        assert!(!winners.is_empty());
+        for seat in 0..4 {
+            assert_eq!(
+                winners.contains(&seat),
+                result.team_scores[seat % 2] == *best
+            );
+        }
+    }
+
+    #[test]
+    fn test_trick_winner_with_duplicates() {
+        let card = Card::new_normal;
+        let trick = [
+            (2, card(Suit::Hearts, Rank::Numeric(5))),
+            (0, card(Suit::Hearts, Rank::King)),
+            (1, card(Suit::Clubs, Rank::Ace)),
+            (3, card(Suit::Hearts, Rank::King)),
+        ];
+        let winner = |cards: &[(usize, Card)], trump: Option<Suit>, rule| {
+            trick_winner(cards, trump.as_ref(), rule)
+        };
+        assert_eq!(winner(&[], None, DuplicateCardRule::Cancel), None);
+        assert_eq!(winner(&trick, None, DuplicateCardRule::FirstWins), Some(0));
+        assert_eq!(winner(&trick, None, DuplicateCardRule::LastWins), Some(3));
+        // the kings cancel, and the ace does not follow suit
+        assert_eq!(winner(&trick, None, DuplicateCardRule::Cancel), Some(2));
+        let trump = Some(Suit::Clubs);
+        for rule in [
+            DuplicateCardRule::FirstWins,
+            DuplicateCardRule::LastWins,
+            DuplicateCardRule::Cancel,
+        ] {
+            assert_eq!(winner(&trick, trump, rule), Some(1));
+        }
+        // with every card cancelled the leader takes the trick
+        let king = card(Suit::Hearts, Rank::King);
+        let cancelled = [(1, king), (0, king)];
+        assert_eq!(winner(&cancelled, None, DuplicateCardRule::Cancel), Some(1));
+        // with the led card cancelled, the suit led still counts
+        let trick = [trick[1], trick[2], trick[3], trick[0]];
+        assert_eq!(winner(&trick, None, DuplicateCardRule::Cancel), Some(2));
+    }
+
+    #[test]
+    fn test_duplicate_rule_in_two_deck_games() {
+        for (rule, second_wins) in [
+            (DuplicateCardRule::FirstWins, false),
+            (DuplicateCardRule::LastWins, true),
+            (DuplicateCardRule::Cancel, false),
+        ] {
+            let config = GameConfig::builder(2, 13)
+                .decks(2)
+                .duplicate_cards(rule)
+                .build()
+                .unwrap();
+            let mut duplicates = 0;
+            for seed in 0..20 {
+                let mut game = Judgment::with_config(config.clone());
+                game.start().unwrap();
+                let mut driver = RandomDriver::new(seed);
+                while let Some(transition) = driver.next_transition(&game) {
+                    for update in game.update(transition).unwrap() {
+                        let StateUpdate::TrickWon { winner, cards } = update else {
+                            continue;
+                        };
+                        if cards[0] != cards[1] {
+                            continue;
+                        }
+                        // with two players, cancelled cards leave the trick
+                        // to the leader, who played first
+                        let (Stage::Play(round) | Stage::PredictScores(round)) = &game.stage else {
+                            continue;
+                        };
+                        let trick = round.completed_tricks.last().unwrap();
+                        assert_eq!(trick.winner, winner);
+                        let second = trick.cards[1].0;
+                        assert_eq!(winner == second, second_wins, "{rule:?}");
+                        duplicates += 1;
+                    }
+                }
+            }
+            assert!(duplicates > 0, "no trick with identical cards was played");
+        }
+    }
 
     #[test]
     fn test_trick_card_comparison_without_trump() {
@@ -0,0 +1,1037 @@
+use std::{fmt, str::FromStr};
+
+use card_deck::standard_deck::{Card, Rank, Suit};
+
+use crate::{
+    BiddingMode, ClaimRule, DuplicateCardRule, GameConfig, HandSizeSchedule, InvalidTransition,
+    Judgment, NotationError, NotationErrorKind, Phase, Scoring, TeamBid, Teams, Transition,
+    TrumpSelection,
+};
+
+/// A game written down as the rules it is played with and the transitions
+/// it went through, with a text notation for sharing and archiving it.
+///
+/// The notation starts with a header of `[Tag "value"]` lines holding the
+/// rules and the seed of every deal, followed by the rounds:
+///
+/// ```text
+/// [Players "3"]
+/// [MaxHandSize "2"]
+/// ...
+/// [Seeds "0101...01 0202...02"]
+///
+/// Round 1
+/// Deal
+/// Bids 0:1 1:0 2:0
+/// Trick A♠ 3♠ 10♠
+/// Trick 4♥ K♥ 2♥
+///
+/// Round 2
+/// ...
+/// ```
+///
+/// `Blind` lists the predictions made before the deal and `Bids` the others,
+/// as `player:score`. Every `Trick` lists the cards of one trick in the
+/// order they were played, starting with the leader, as shown by their
+/// [`Display`](fmt::Display) implementation. A claim for the rest of the
+/// round is written as `Claim player`, followed by `Accept` and `Reject` lines listing
+/// the players who answered it. Blank lines and lines starting with `;` are
+/// ignored.
+///
+/// # Examples
+/// ```
+/// use judgment::{GameRecord, Judgment, Transition};
+///
+/// let mut game = Judgment::new(2, 3, None);
+/// game.start().unwrap();
+/// game.update(Transition::Deal { seed: [5; 32] }).unwrap();
+/// game.update(Transition::PredictScore { player: 0, score: 1 }).unwrap();
+///
+/// let notation = game.record().to_string();
+/// let record: GameRecord = notation.parse().unwrap();
+/// assert_eq!(record, game.record());
+/// assert_eq!(record.replay().unwrap().history(), game.history());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub config: GameConfig,
+    pub transitions: Vec<Transition>,
+}
+
+impl GameRecord {
+    pub fn new(config: GameConfig, transitions: Vec<Transition>) -> Self {
+        GameRecord {
+            config,
+            transitions,
+        }
+    }
+
+    /// Read a record in the notation described on [`GameRecord`].
+    ///
+    /// The parser is strict: every tag must be known and appear at most once,
+    /// rounds must be numbered in order, every trick but the very last must
+    /// be complete, and every move must be legal when it is made.
+    ///
+    /// # Errors
+    /// Errors with the line and column of the first problem in the `text`.
+    pub fn parse(text: &str) -> Result<Self, NotationError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with(';'))
+            .peekable();
+        let mut header = Header::default();
+        while let Some((number, line)) = lines.next_if(|(_, line)| line.starts_with('[')) {
+            header.read_tag(number, line)?;
+        }
+        let (config, mut seeds) = header.finish()?;
+        let mut game = Judgment::with_config(config.clone());
+        game.start().expect("a new game can always be started");
+        let mut announced = 0;
+        while let Some((number, line)) = lines.next() {
+            let tokens = tokens(line, 1);
+            let keyword = &tokens[0];
+            let at = |token: &Token, kind| NotationError {
+                line: number,
+                column: token.column,
+                kind,
+            };
+            let completed = game.round_results.len();
+            if keyword.text == "Round" {
+                let found = match &tokens[1..] {
+                    [round] => round.text.parse().map_err(|_| {
+                        at(
+                            round,
+                            NotationErrorKind::unexpected("a round number", round.text),
+                        )
+                    })?,
+                    _ => {
+                        return Err(at(
+                            keyword,
+                            NotationErrorKind::unexpected("`Round` and its number", line.trim()),
+                        ))
+                    }
+                };
+                if announced != completed || game.phase() != Phase::Dealing {
+                    return Err(at(
+                        keyword,
+                        NotationErrorKind::unexpected("a move", keyword.text),
+                    ));
+                }
+                let expected = completed + 1;
+                if found != expected {
+                    return Err(at(
+                        keyword,
+                        NotationErrorKind::RoundOutOfOrder { expected, found },
+                    ));
+                }
+                announced = expected;
+                continue;
+            }
+            if announced == completed && !game.is_over() {
+                return Err(at(
+                    keyword,
+                    NotationErrorKind::unexpected("`Round` and its number", keyword.text),
+                ));
+            }
+            match keyword.text {
+                "Deal" => {
+                    if let Some(token) = tokens.get(1) {
+                        return Err(at(
+                            token,
+                            NotationErrorKind::unexpected("the end of the line", token.text),
+                        ));
+                    }
+                    let seed = seeds
+                        .next()
+                        .ok_or_else(|| at(keyword, NotationErrorKind::MissingSeed))?;
+                    game.update(Transition::Deal { seed })
+                        .map_err(|err| at(keyword, NotationErrorKind::IllegalTransition(err)))?;
+                }
+                "Blind" | "Bids" => {
+                    if tokens.len() == 1 {
+                        return Err(at(
+                            keyword,
+                            NotationErrorKind::unexpected("a prediction", "the end of the line"),
+                        ));
+                    }
+                    for token in &tokens[1..] {
+                        let (player, score) = read_prediction(token.text).ok_or_else(|| {
+                            at(
+                                token,
+                                NotationErrorKind::unexpected(
+                                    "a prediction like `2:1`",
+                                    token.text,
+                                ),
+                            )
+                        })?;
+                        let transition = if keyword.text == "Blind" {
+                            Transition::PredictBlind { player, score }
+                        } else {
+                            Transition::PredictScore { player, score }
+                        };
+                        game.update(transition)
+                            .map_err(|err| at(token, NotationErrorKind::IllegalTransition(err)))?;
+                    }
+                }
+                "Trick" => {
+                    if tokens.len() == 1 {
+                        return Err(at(
+                            keyword,
+                            NotationErrorKind::unexpected("a card", "the end of the line"),
+                        ));
+                    }
+                    if game.trick().iter().any(Option::is_some) {
+                        return Err(at(keyword, NotationErrorKind::IncompleteTrick));
+                    }
+                    for token in &tokens[1..] {
+                        let card = read_card(token.text).ok_or_else(|| {
+                            at(
+                                token,
+                                NotationErrorKind::UnknownCard(token.text.to_string()),
+                            )
+                        })?;
+                        // a move out of turn is rejected by the game itself
+                        let player = game.current_player().unwrap_or_default();
+                        game.update(Transition::Play { player, card })
+                            .map_err(|err| at(token, NotationErrorKind::IllegalTransition(err)))?;
+                    }
+                    if game.trick().iter().any(Option::is_some) && lines.peek().is_some() {
+                        return Err(at(keyword, NotationErrorKind::IncompleteTrick));
+                    }
+                }
+                "Claim" => {
+                    let token = match &tokens[1..] {
+                        [token] => token,
+                        _ => {
+                            return Err(at(
+                                keyword,
+                                NotationErrorKind::unexpected("`Claim` and a player", line.trim()),
+                            ))
+                        }
+                    };
+                    let player = token.text.parse().map_err(|_| {
+                        at(token, NotationErrorKind::unexpected("a player", token.text))
+                    })?;
+                    game.update(Transition::Claim { player })
+                        .map_err(|err| at(token, NotationErrorKind::IllegalTransition(err)))?;
+                }
+                "Accept" | "Reject" => {
+                    if tokens.len() == 1 {
+                        return Err(at(
+                            keyword,
+                            NotationErrorKind::unexpected("a player", "the end of the line"),
+                        ));
+                    }
+                    for token in &tokens[1..] {
+                        let player = token.text.parse().map_err(|_| {
+                            at(token, NotationErrorKind::unexpected("a player", token.text))
+                        })?;
+                        let transition = if keyword.text == "Accept" {
+                            Transition::AcceptClaim { player }
+                        } else {
+                            Transition::RejectClaim { player }
+                        };
+                        game.update(transition)
+                            .map_err(|err| at(token, NotationErrorKind::IllegalTransition(err)))?;
+                    }
+                }
+                _ => return Err(at(
+                    keyword,
+                    NotationErrorKind::unexpected(
+                        "`Round`, `Blind`, `Deal`, `Bids`, `Trick`, `Claim`, `Accept` or `Reject`",
+                        keyword.text,
+                    ),
+                )),
+            }
+        }
+        if let Some((line, column)) = seeds.unused() {
+            return Err(NotationError {
+                line,
+                column,
+                kind: NotationErrorKind::UnusedSeed,
+            });
+        }
+        Ok(GameRecord {
+            config,
+            transitions: game.history,
+        })
+    }
+
+    /// Recreate the game, see [`Judgment::replay`].
+    ///
+    /// # Errors
+    /// Errors with the first illegal transition, which cannot happen for a
+    /// record read by [`GameRecord::parse`].
+    pub fn replay(&self) -> Result<Judgment, InvalidTransition> {
+        Judgment::replay(self.config.clone(), &self.transitions)
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = NotationError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        GameRecord::parse(text)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    /// Write the record in the notation described on [`GameRecord`]. A game
+    /// played with [`Scoring::Custom`] is written, but cannot be read back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let config = &self.config;
+        let tag = |f: &mut fmt::Formatter<'_>, name: &str, value: &dyn fmt::Display| {
+            writeln!(f, "[{name} \"{value}\"]")
+        };
+        tag(f, "Players", &config.players())?;
+        tag(f, "MaxHandSize", &config.max_hand_size())?;
+        tag(f, "Decks", &config.decks())?;
+        tag(f, "Jokers", &config.jokers())?;
+        let schedule = match config.hand_size_schedule() {
+            HandSizeSchedule::Descending => "descending",
+            HandSizeSchedule::Ascending => "ascending",
+            HandSizeSchedule::UpAndDown => "up-and-down",
+        };
+        tag(f, "HandSizes", &schedule)?;
+        let rotation: Vec<&str> = config
+            .trump_rotation()
+            .iter()
+            .map(|suit| suit_name(suit.as_ref()))
+            .collect();
+        tag(f, "TrumpRotation", &rotation.join(" "))?;
+        let selection = match config.trump_selection() {
+            TrumpSelection::Rotation => "rotation",
+            TrumpSelection::TurnUp => "turn-up",
+        };
+        tag(f, "TrumpSelection", &selection)?;
+        tag(
+            f,
+            "LastPlayerRestriction",
+            &if config.last_player_restriction() {
+                "yes"
+            } else {
+                "no"
+            },
+        )?;
+        let scoring = match config.scoring() {
+            Scoring::Standard => "standard".to_string(),
+            Scoring::TenPlusBid => "ten-plus-bid".to_string(),
+            Scoring::PenaltyPerTrick { hit_bonus, penalty } => {
+                format!("penalty-per-trick {hit_bonus} {penalty}")
+            }
+            Scoring::ZeroBidBonus { bonus } => format!("zero-bid-bonus {bonus}"),
+            Scoring::Custom(_) => "custom".to_string(),
+        };
+        tag(f, "Scoring", &scoring)?;
+        let duplicates = match config.duplicate_cards() {
+            DuplicateCardRule::FirstWins => "first-wins",
+            DuplicateCardRule::LastWins => "last-wins",
+            DuplicateCardRule::Cancel => "cancel",
+        };
+        tag(f, "DuplicateCards", &duplicates)?;
+        let bidding = match config.bidding() {
+            BiddingMode::Sequential => "sequential",
+            BiddingMode::Sealed => "sealed",
+        };
+        tag(f, "Bidding", &bidding)?;
+        match config.blind_multiplier() {
+            Some(multiplier) => tag(f, "BlindPredictions", &multiplier)?,
+            None => tag(f, "BlindPredictions", &"no")?,
+        }
+        if let Some(teams) = config.teams() {
+            let seats: Vec<String> = teams.seats().iter().map(usize::to_string).collect();
+            tag(f, "Teams", &seats.join(" "))?;
+            let bid = match teams.bid() {
+                TeamBid::Sum => "sum",
+                TeamBid::Joint => "joint",
+            };
+            tag(f, "TeamBid", &bid)?;
+        }
+        let claims = match config.claims() {
+            ClaimRule::Agreement => "agreement",
+            ClaimRule::Verified => "verified",
+        };
+        tag(f, "Claims", &claims)?;
+        let seeds: Vec<String> = self
+            .transitions
+            .iter()
+            .filter_map(|transition| match transition {
+                Transition::Deal { seed } => {
+                    Some(seed.iter().map(|byte| format!("{byte:02x}")).collect())
+                }
+                _ => None,
+            })
+            .collect();
+        tag(f, "Seeds", &seeds.join(" "))?;
+
+        // replay the game to know where rounds and tricks begin
+        let mut game = Judgment::with_config(config.clone());
+        let _ = game.start();
+        let mut announced = 0;
+        let mut line: Option<&str> = None;
+        for transition in &self.transitions {
+            let round = game.round_results.len() + 1;
+            if game.phase() == Phase::Dealing && announced < round {
+                if line.take().is_some() {
+                    writeln!(f)?;
+                }
+                write!(f, "\nRound {round}")?;
+                announced = round;
+            }
+            let (keyword, token) = match transition {
+                Transition::Deal { .. } => ("Deal", None),
+                Transition::PredictBlind { player, score } => {
+                    ("Blind", Some(format!("{player}:{score}")))
+                }
+                Transition::PredictScore { player, score } => {
+                    ("Bids", Some(format!("{player}:{score}")))
+                }
+                Transition::Play { card, .. } => ("Trick", Some(card.to_string())),
+                Transition::Claim { player } => ("Claim", Some(player.to_string())),
+                Transition::AcceptClaim { player } => ("Accept", Some(player.to_string())),
+                Transition::RejectClaim { player } => ("Reject", Some(player.to_string())),
+            };
+            let new_trick = keyword == "Trick" && game.trick().iter().all(Option::is_none);
+            if line != Some(keyword) || new_trick || keyword == "Deal" || keyword == "Claim" {
+                write!(f, "\n{keyword}")?;
+                line = Some(keyword);
+            }
+            if let Some(token) = token {
+                write!(f, " {token}")?;
+            }
+            let _ = game.update(*transition);
+        }
+        if announced > 0 {
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Judgment {
+    /// The rules and history of the game, which can be written down in a
+    /// text notation, see [`GameRecord`].
+    pub fn record(&self) -> GameRecord {
+        GameRecord::new(self.config.clone(), self.history.clone())
+    }
+}
+
+struct Token<'a> {
+    text: &'a str,
+    column: usize,
+}
+
+/// The whitespace separated words of `text`, numbering the columns from
+/// `first_column`.
+fn tokens(text: &str, first_column: usize) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (column, (offset, character)) in text.char_indices().enumerate() {
+        match (character.is_whitespace(), start) {
+            (false, None) => start = Some((offset, column)),
+            (true, Some((begin, begin_column))) => {
+                tokens.push(Token {
+                    text: &text[begin..offset],
+                    column: first_column + begin_column,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((begin, begin_column)) = start {
+        tokens.push(Token {
+            text: &text[begin..],
+            column: first_column + begin_column,
+        });
+    }
+    tokens
+}
+
+fn suit_name(suit: Option<&Suit>) -> &'static str {
+    match suit {
+        Some(Suit::Clubs) => "clubs",
+        Some(Suit::Diamonds) => "diamonds",
+        Some(Suit::Hearts) => "hearts",
+        Some(Suit::Spades) => "spades",
+        None => "none",
+    }
+}
+
+fn read_prediction(text: &str) -> Option<(usize, u8)> {
+    let (player, score) = text.split_once(':')?;
+    Some((player.parse().ok()?, score.parse().ok()?))
+}
+
+/// The card shown as `text` by its [`Display`](fmt::Display) implementation.
+fn read_card(text: &str) -> Option<Card> {
+    let ranks = (2..=10)
+        .map(Rank::Numeric)
+        .chain([Rank::Jack, Rank::Queen, Rank::King, Rank::Ace]);
+    ranks
+        .flat_map(|rank| {
+            [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds]
+                .map(|suit| Card::new_normal(suit, rank))
+        })
+        .chain([Card::new_joker()])
+        .find(|card| card.to_string() == text)
+}
+
+/// The tags read so far, with the line each was found on.
+#[derive(Default)]
+struct Header<'a> {
+    tags: Vec<(usize, &'a str, Token<'a>)>,
+}
+
+/// The seeds of the header, handed out to the deals in order.
+struct Seeds {
+    seeds: std::vec::IntoIter<([u8; 32], usize, usize)>,
+}
+
+impl Seeds {
+    fn next(&mut self) -> Option<[u8; 32]> {
+        self.seeds.next().map(|(seed, _, _)| seed)
+    }
+
+    /// The line and column of the first seed no deal used, if any.
+    fn unused(&mut self) -> Option<(usize, usize)> {
+        self.seeds.next().map(|(_, line, column)| (line, column))
+    }
+}
+
+const TAGS: [&str; 16] = [
+    "Players",
+    "MaxHandSize",
+    "Decks",
+    "Jokers",
+    "HandSizes",
+    "TrumpRotation",
+    "TrumpSelection",
+    "LastPlayerRestriction",
+    "Scoring",
+    "DuplicateCards",
+    "Bidding",
+    "BlindPredictions",
+    "Teams",
+    "TeamBid",
+    "Claims",
+    "Seeds",
+];
+
+impl<'a> Header<'a> {
+    /// Read a `[Tag "value"]` line.
+    fn read_tag(&mut self, number: usize, line: &'a str) -> Result<(), NotationError> {
+        let error = |column, kind| NotationError {
+            line: number,
+            column,
+            kind,
+        };
+        let malformed = |column, found: &str| {
+            error(
+                column,
+                NotationErrorKind::unexpected("a tag like `[Players \"4\"]`", found),
+            )
+        };
+        let inner = line
+            .trim_end()
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+            .ok_or_else(|| malformed(1, line))?;
+        let (name, quoted) = inner.split_once(' ').ok_or_else(|| malformed(2, inner))?;
+        let value_column = name.chars().count() + 4;
+        let value = quoted
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .filter(|value| !value.contains('"'))
+            .ok_or_else(|| malformed(value_column - 1, quoted))?;
+        if !TAGS.contains(&name) {
+            return Err(error(2, NotationErrorKind::UnknownTag(name.to_string())));
+        }
+        if self.tags.iter().any(|(_, tag, _)| *tag == name) {
+            return Err(error(2, NotationErrorKind::DuplicateTag(name.to_string())));
+        }
+        self.tags.push((
+            number,
+            name,
+            Token {
+                text: value,
+                column: value_column,
+            },
+        ));
+        Ok(())
+    }
+
+    /// The rules and seeds the header describes.
+    fn finish(self) -> Result<(GameConfig, Seeds), NotationError> {
+        let get = |name: &str| self.tags.iter().find(|(_, tag, _)| *tag == name);
+        let invalid = |(line, tag, value): &(usize, &str, Token<'_>)| NotationError {
+            line: *line,
+            column: value.column,
+            kind: NotationErrorKind::InvalidValue {
+                tag: tag.to_string(),
+                value: value.text.to_string(),
+            },
+        };
+        let number = |name: &str| -> Result<Option<u8>, NotationError> {
+            get(name)
+                .map(|tag| tag.2.text.parse().map_err(|_| invalid(tag)))
+                .transpose()
+        };
+        let required = |name: &'static str| {
+            number(name)?.ok_or(NotationError {
+                line: 1,
+                column: 1,
+                kind: NotationErrorKind::MissingTag(name.to_string()),
+            })
+        };
+        let choice = |name: &str, values: &[&str]| -> Result<Option<usize>, NotationError> {
+            get(name)
+                .map(|tag| {
+                    values
+                        .iter()
+                        .position(|value| *value == tag.2.text)
+                        .ok_or_else(|| invalid(tag))
+                })
+                .transpose()
+        };
+
+        let mut builder = GameConfig::builder(required("Players")?, required("MaxHandSize")?);
+        if let Some(decks) = number("Decks")? {
+            builder = builder.decks(decks);
+        }
+        if let Some(jokers) = number("Jokers")? {
+            builder = builder.jokers(jokers);
+        }
+        if let Some(schedule) = choice("HandSizes", &["descending", "ascending", "up-and-down"])? {
+            builder = builder.hand_sizes(
+                [
+                    HandSizeSchedule::Descending,
+                    HandSizeSchedule::Ascending,
+                    HandSizeSchedule::UpAndDown,
+                ][schedule],
+            );
+        }
+        if let Some(tag) = get("TrumpRotation") {
+            let suits = [
+                Some(Suit::Spades),
+                Some(Suit::Hearts),
+                Some(Suit::Clubs),
+                Some(Suit::Diamonds),
+                None,
+            ];
+            let rotation = tag
+                .2
+                .text
+                .split_whitespace()
+                .map(|name| {
+                    suits
+                        .into_iter()
+                        .find(|suit| suit_name(suit.as_ref()) == name)
+                        .ok_or_else(|| invalid(tag))
+                })
+                .collect::<Result<_, _>>()?;
+            builder = builder.trump_rotation(rotation);
+        }
+        if let Some(selection) = choice("TrumpSelection", &["rotation", "turn-up"])? {
+            builder = builder
+                .trump_selection([TrumpSelection::Rotation, TrumpSelection::TurnUp][selection]);
+        }
+        if let Some(restriction) = choice("LastPlayerRestriction", &["no", "yes"])? {
+            builder = builder.last_player_restriction(restriction == 1);
+        }
+        if let Some(tag) = get("Scoring") {
+            let words: Vec<&str> = tag.2.text.split(' ').collect();
+            let parameter = |index: usize| -> Result<i64, NotationError> {
+                words[index].parse().map_err(|_| invalid(tag))
+            };
+            let scoring = match words[..] {
+                ["standard"] => Scoring::Standard,
+                ["ten-plus-bid"] => Scoring::TenPlusBid,
+                ["penalty-per-trick", _, _] => Scoring::PenaltyPerTrick {
+                    hit_bonus: parameter(1)?,
+                    penalty: parameter(2)?,
+                },
+                ["zero-bid-bonus", _] => Scoring::ZeroBidBonus {
+                    bonus: parameter(1)?,
+                },
+                _ => return Err(invalid(tag)),
+            };
+            builder = builder.scoring(scoring);
+        }
+        if let Some(rule) = choice("DuplicateCards", &["first-wins", "last-wins", "cancel"])? {
+            builder = builder.duplicate_cards(
+                [
+                    DuplicateCardRule::FirstWins,
+                    DuplicateCardRule::LastWins,
+                    DuplicateCardRule::Cancel,
+                ][rule],
+            );
+        }
+        if let Some(mode) = choice("Bidding", &["sequential", "sealed"])? {
+            builder = builder.bidding([BiddingMode::Sequential, BiddingMode::Sealed][mode]);
+        }
+        if let Some(tag) = get("BlindPredictions") {
+            if tag.2.text != "no" {
+                builder = builder.blind_predictions(tag.2.text.parse().map_err(|_| invalid(tag))?);
+            }
+        }
+        let bid = choice("TeamBid", &["sum", "joint"])?;
+        match get("Teams") {
+            Some(tag) => {
+                let seats = tag
+                    .2
+                    .text
+                    .split_whitespace()
+                    .map(|seat| seat.parse().map_err(|_| invalid(tag)))
+                    .collect::<Result<_, _>>()?;
+                let bid = [TeamBid::Sum, TeamBid::Joint][bid.unwrap_or_default()];
+                builder = builder.teams(Teams::new(seats, bid));
+            }
+            None => {
+                if let Some(tag) = get("TeamBid") {
+                    return Err(invalid(tag));
+                }
+            }
+        }
+        if let Some(rule) = choice("Claims", &["agreement", "verified"])? {
+            builder = builder.claims([ClaimRule::Agreement, ClaimRule::Verified][rule]);
+        }
+        let config = builder.build().map_err(|err| NotationError {
+            line: self.tags.first().map_or(1, |(line, _, _)| *line),
+            column: 1,
+            kind: NotationErrorKind::InvalidConfig(err),
+        })?;
+
+        let mut seeds = Vec::new();
+        if let Some((line, _, value)) = get("Seeds") {
+            for token in tokens(value.text, value.column) {
+                let seed = read_seed(token.text).ok_or(NotationError {
+                    line: *line,
+                    column: token.column,
+                    kind: NotationErrorKind::unexpected(
+                        "a seed of 64 hexadecimal digits",
+                        token.text,
+                    ),
+                })?;
+                seeds.push((seed, *line, token.column));
+            }
+        }
+        Ok((
+            config,
+            Seeds {
+                seeds: seeds.into_iter(),
+            },
+        ))
+    }
+}
+
+fn read_seed(text: &str) -> Option<[u8; 32]> {
+    if text.len() != 64 || !text.is_ascii() {
+        return None;
+    }
+    let mut seed = [0; 32];
+    for (index, byte) in seed.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[2 * index..2 * index + 2], 16).ok()?;
+    }
+    Some(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use card_deck::standard_deck::{Card, Rank, Suit};
+
+    use crate::{
+        BiddingMode, GameConfig, InvalidTransition, Judgment, NotationError, NotationErrorKind,
+        RandomDriver, Scoring, TeamBid, Teams, Transition, TrumpSelection,
+    };
+
+    use super::{read_card, GameRecord};
+
+    fn played_game(config: GameConfig, seed: u64, moves: Option<usize>) -> Judgment {
+        let mut driver = RandomDriver::new(seed);
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        while !game.is_over() && moves.is_none_or(|moves| game.history().len() < moves) {
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        game
+    }
+
+    fn error_at(text: &str) -> (usize, usize, NotationErrorKind) {
+        let NotationError { line, column, kind } = GameRecord::parse(text).unwrap_err();
+        (line, column, kind)
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let configs = [
+            GameConfig::builder(3, 4).build().unwrap(),
+            GameConfig::builder(4, 5)
+                .decks(2)
+                .jokers(2)
+                .trump_selection(TrumpSelection::TurnUp)
+                .scoring(Scoring::PenaltyPerTrick {
+                    hit_bonus: 10,
+                    penalty: -2,
+                })
+                .blind_predictions(3)
+                .teams(Teams::new(vec![0, 1, 0, 1], TeamBid::Joint))
+                .build()
+                .unwrap(),
+            GameConfig::builder(3, 3)
+                .bidding(BiddingMode::Sealed)
+                .trump_rotation(vec![None, Some(Suit::Hearts)])
+                .scoring(Scoring::ZeroBidBonus { bonus: 5 })
+                .build()
+                .unwrap(),
+        ];
+        for (seed, config) in configs.into_iter().enumerate() {
+            for moves in [None, Some(9)] {
+                let game = played_game(config.clone(), seed as u64, moves);
+                let text = game.record().to_string();
+                let record = GameRecord::parse(&text).unwrap_or_else(|err| panic!("{err}\n{text}"));
+                assert_eq!(record, game.record());
+                let replayed = record.replay().unwrap();
+                assert_eq!(replayed.scores(), game.scores());
+                assert_eq!(replayed.record().to_string(), text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_record_layout() {
+        let mut game = Judgment::new(2, 1, None);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [171; 32] }).unwrap();
+        game.update(Transition::PredictScore {
+            player: 0,
+            score: 1,
+        })
+        .unwrap();
+        game.update(Transition::PredictScore {
+            player: 1,
+            score: 1,
+        })
+        .unwrap();
+        let first = game.legal_cards(0)[0];
+        game.update(Transition::Play {
+            player: 0,
+            card: first,
+        })
+        .unwrap();
+        let second = game.legal_cards(1)[0];
+        game.update(Transition::Play {
+            player: 1,
+            card: second,
+        })
+        .unwrap();
+        let text = game.record().to_string();
+        let moves = format!("Round 1\nDeal\nBids 0:1 1:1\nTrick {first} {second}\n");
+        assert!(text.ends_with(&format!("[Seeds \"{}\"]\n\n{moves}", "ab".repeat(32))));
+        assert!(text.starts_with("[Players \"2\"]\n[MaxHandSize \"1\"]\n[Decks \"1\"]\n"));
+        // comments, blank lines and missing optional tags are fine
+        let short = format!(
+            "; a quick game\n[Players \"2\"]\n[MaxHandSize \"1\"]\n[Seeds \"{}\"]\n\n{moves}",
+            "ab".repeat(32)
+        );
+        assert_eq!(GameRecord::parse(&short), Ok(game.record()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let seed = "00".repeat(32);
+        let header = format!("[Players \"2\"]\n[MaxHandSize \"2\"]\n[Seeds \"{seed}\"]\n");
+        assert_eq!(
+            error_at("[Players \"2\"]\n[Player \"3\"]\n"),
+            (2, 2, NotationErrorKind::UnknownTag("Player".to_string()))
+        );
+        assert_eq!(
+            error_at("[Players \"2\"]\n[Players \"3\"]\n"),
+            (2, 2, NotationErrorKind::DuplicateTag("Players".to_string()))
+        );
+        assert_eq!(
+            error_at("[Players 2]\n"),
+            (
+                1,
+                10,
+                NotationErrorKind::unexpected("a tag like `[Players \"4\"]`", "2")
+            )
+        );
+        assert_eq!(
+            error_at("[Players \"2\"]\n"),
+            (
+                1,
+                1,
+                NotationErrorKind::MissingTag("MaxHandSize".to_string())
+            )
+        );
+        assert_eq!(
+            error_at("[Players \"2\"]\n[MaxHandSize \"2\"]\n[HandSizes \"sideways\"]\n"),
+            (
+                3,
+                13,
+                NotationErrorKind::InvalidValue {
+                    tag: "HandSizes".to_string(),
+                    value: "sideways".to_string()
+                }
+            )
+        );
+        assert_eq!(
+            error_at(&format!(
+                "[Players \"2\"]\n[MaxHandSize \"2\"]\n[Seeds \"{seed} 12\"]\n"
+            )),
+            (
+                3,
+                74,
+                NotationErrorKind::unexpected("a seed of 64 hexadecimal digits", "12")
+            )
+        );
+        assert_eq!(
+            error_at("[Players \"0\"]\n[MaxHandSize \"2\"]\n").2,
+            NotationErrorKind::InvalidConfig(crate::GameConfigError::ZeroPlayers)
+        );
+        assert_eq!(
+            error_at(&format!(
+                "[Players \"2\"]\n[MaxHandSize \"2\"]\n[Teams \"0 {}\"]\n",
+                usize::MAX
+            ))
+            .2,
+            NotationErrorKind::InvalidConfig(crate::GameConfigError::TeamOutOfRange {
+                team: usize::MAX,
+                players: 2
+            })
+        );
+        assert_eq!(
+            error_at(&format!("{header}Deal\n")),
+            (
+                4,
+                1,
+                NotationErrorKind::unexpected("`Round` and its number", "Deal")
+            )
+        );
+        assert_eq!(
+            error_at(&format!("{header}Round 2\n")),
+            (
+                4,
+                1,
+                NotationErrorKind::RoundOutOfOrder {
+                    expected: 1,
+                    found: 2
+                }
+            )
+        );
+        assert_eq!(
+            error_at(&format!("{header}Round 1\nDeal\nDeal\n")),
+            (6, 1, NotationErrorKind::MissingSeed)
+        );
+        assert_eq!(
+            error_at(&format!("{header}Round 1\n")),
+            (3, 9, NotationErrorKind::UnusedSeed)
+        );
+        assert_eq!(
+            error_at(&format!("{header}Round 1\nDeal\nBids 0:1 1-1\n")),
+            (
+                6,
+                10,
+                NotationErrorKind::unexpected("a prediction like `2:1`", "1-1")
+            )
+        );
+        assert_eq!(
+            error_at(&format!("{header}Round 1\nDeal\nBids 0:1 0:1\n")),
+            (
+                6,
+                10,
+                NotationErrorKind::IllegalTransition(InvalidTransition::OutOfTurnPlay)
+            )
+        );
+        assert_eq!(
+            error_at(&format!("{header}Round 1\nDeal\nBids 0:1 1:0\nTrick  1X\n")),
+            (7, 8, NotationErrorKind::UnknownCard("1X".to_string()))
+        );
+        assert_eq!(
+            error_at(&format!("{header}Round 1\nDeal\nBids 0:1 1:0\nShuffle\n")).2,
+            NotationErrorKind::unexpected(
+                "`Round`, `Blind`, `Deal`, `Bids`, `Trick`, `Claim`, `Accept` or `Reject`",
+                "Shuffle"
+            )
+        );
+
+        // a trick must be complete unless the record ends with it
+        let mut game = Judgment::new(2, 2, None);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [0; 32] }).unwrap();
+        game.update(Transition::PredictScore {
+            player: 0,
+            score: 1,
+        })
+        .unwrap();
+        game.update(Transition::PredictScore {
+            player: 1,
+            score: 0,
+        })
+        .unwrap();
+        let lead = game.legal_cards(0)[0];
+        let moves = format!("{header}Round 1\nDeal\nBids 0:1 1:0\nTrick {lead}\n");
+        assert_eq!(GameRecord::parse(&moves).unwrap().transitions.len(), 4);
+        assert_eq!(
+            error_at(&format!("{moves}Trick {lead}\n")),
+            (7, 1, NotationErrorKind::IncompleteTrick)
+        );
+        let wrong = game
+            .hand_of_player(0)
+            .unwrap()
+            .iter()
+            .find(|card| **card != lead)
+            .copied()
+            .unwrap();
+        let (_, _, kind) = error_at(&format!(
+            "{header}Round 1\nDeal\nBids 0:1 1:0\nTrick {wrong} {wrong}\n"
+        ));
+        assert!(matches!(kind, NotationErrorKind::IllegalTransition(_)));
+    }
+
+    #[test]
+    fn test_claims_are_recorded() {
+        let config = GameConfig::builder(3, 2).build().unwrap();
+        let mut game = played_game(config, 6, Some(4));
+        let leader = game.current_player().unwrap();
+        game.update(Transition::Claim { player: leader }).unwrap();
+        let opponents: Vec<usize> = (0..3).filter(|player| *player != leader).collect();
+        game.update(Transition::AcceptClaim {
+            player: opponents[0],
+        })
+        .unwrap();
+        game.update(Transition::RejectClaim {
+            player: opponents[1],
+        })
+        .unwrap();
+        game.update(Transition::Claim { player: leader }).unwrap();
+        for player in opponents.iter().copied() {
+            game.update(Transition::AcceptClaim { player }).unwrap();
+        }
+        assert_eq!(game.round_results.len(), 1);
+        let text = game.record().to_string();
+        assert!(text.contains("[Claims \"agreement\"]\n"));
+        let claim = format!("Claim {leader}\n");
+        assert!(text.contains(&format!(
+            "\n{claim}Accept {}\nReject {}\n{claim}Accept {} {}\n",
+            opponents[0], opponents[1], opponents[0], opponents[1]
+        )));
+        assert_eq!(GameRecord::parse(&text), Ok(game.record()));
+    }
+
+    #[test]
+    fn test_cards_are_read_as_displayed() {
+        for card in [
+            Card::new_normal(Suit::Hearts, Rank::Numeric(10)),
+            Card::new_normal(Suit::Clubs, Rank::Queen),
+            Card::new_joker(),
+        ] {
+            assert_eq!(read_card(&card.to_string()), Some(card));
+        }
+        assert_eq!(read_card("11"), None);
+    }
+}
@@ -3,6 +3,7 @@ use card_deck::standard_deck::{Card, Suit};
 use crate::card_comparator;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct Player {
     hand: Vec<Card>,
 }
@@ -34,9 +35,16 @@ impl Player {
         self.search(card).map(|position| self.hand.remove(position))
     }
 
+    /// Whether playing `card` follows suit when `first_card` was led, i.e. it
+    /// is of the same suit or the player has no card of that suit. Jokers
+    /// have no suit and may always be played.
+    pub(crate) fn follows_suit(&self, card: &Card, first_card: &Card) -> bool {
+        card.is_joker()
+            || card.suit() == first_card.suit()
+            || first_card.suit().is_none_or(|suit| !self.has_suit(suit))
+    }
+
     pub(crate) fn has_suit(&self, suit: &Suit) -> bool {
-        self.hand
-            .iter()
-            .any(|h_card| h_card.suit().unwrap() == suit)
+        self.hand.iter().any(|h_card| h_card.suit() == Some(suit))
     }
 }
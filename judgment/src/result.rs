@@ -0,0 +1,237 @@
+use card_deck::standard_deck::Suit;
+
+use crate::{Judgment, Stage, Teams};
+
+/// How a finished game turned out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameResult {
+    /// Every player from the highest score to the lowest. Tied players keep
+    /// their seating order.
+    pub ranking: Vec<Standing>,
+    /// Every round of the game, in the order they were played.
+    pub rounds: Vec<RoundResult>,
+    /// Prediction statistics of every player, indexed by player.
+    pub stats: Vec<PlayerStats>,
+    /// The teams the players were split into, if they played in teams.
+    pub teams: Option<Teams>,
+    /// The final score of every team, empty unless they played in teams.
+    pub team_scores: Vec<i64>,
+}
+
+impl GameResult {
+    /// The players sharing the first rank, or the players of the teams with
+    /// the highest score when playing in teams.
+    pub fn winners(&self) -> Vec<usize> {
+        let mut scores = vec![0; self.ranking.len()];
+        for standing in &self.ranking {
+            scores[standing.player] = standing.score;
+        }
+        winners(&scores, self.teams.as_ref(), &self.team_scores)
+    }
+}
+
+/// The players with the highest score, or in the teams with the highest
+/// score if there are `teams`, in seating order.
+pub(crate) fn winners(scores: &[i64], teams: Option<&Teams>, team_scores: &[i64]) -> Vec<usize> {
+    match teams {
+        Some(teams) => {
+            let best = team_scores.iter().max();
+            (0..scores.len())
+                .filter(|seat| team_scores.get(teams.team_of(*seat)) == best)
+                .collect()
+        }
+        None => {
+            let best = scores.iter().max();
+            (0..scores.len())
+                .filter(|seat| scores.get(*seat) == best)
+                .collect()
+        }
+    }
+}
+
+/// The final position of a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Standing {
+    /// 1 for the best score. Tied players share a rank and the ranks after
+    /// them are skipped, e.g. 1, 1, 3.
+    pub rank: usize,
+    pub player: usize,
+    pub score: i64,
+}
+
+/// The outcome of a single round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundResult {
+    pub hand_size: u8,
+    pub trump_suit: Option<Suit>,
+    pub predictions: Vec<u8>,
+    pub tricks: Vec<u8>,
+    /// Points gained or lost by every player in this round.
+    pub deltas: Vec<i64>,
+    /// The players who predicted blind, whose deltas were multiplied.
+    pub blind: Vec<bool>,
+    /// Points gained or lost by every team in this round, empty unless the
+    /// players play in teams.
+    pub team_deltas: Vec<i64>,
+}
+
+/// How often a player predicted their tricks correctly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerStats {
+    /// Rounds in which the player won exactly as many tricks as predicted.
+    pub hits: usize,
+    pub rounds: usize,
+}
+
+impl PlayerStats {
+    /// Fraction of rounds predicted correctly, 0 if no round was played.
+    pub fn hit_rate(&self) -> f64 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.rounds as f64
+        }
+    }
+}
+
+impl Judgment {
+    /// The final ranking and the round by round breakdown of the game, or
+    /// `None` if the game is not over yet.
+    pub fn result(&self) -> Option<GameResult> {
+        if !matches!(self.stage, Stage::Over) {
+            return None;
+        }
+        let mut ranking: Vec<Standing> = self
+            .scores
+            .iter()
+            .enumerate()
+            .map(|(player, score)| Standing {
+                rank: 1 + self.scores.iter().filter(|other| *other > score).count(),
+                player,
+                score: *score,
+            })
+            .collect();
+        ranking.sort_by_key(|standing| standing.rank);
+        let stats = (0..self.scores.len())
+            .map(|player| PlayerStats {
+                hits: self
+                    .round_results
+                    .iter()
+                    .filter(|round| round.predictions[player] == round.tricks[player])
+                    .count(),
+                rounds: self.round_results.len(),
+            })
+            .collect();
+        Some(GameResult {
+            ranking,
+            rounds: self.round_results.clone(),
+            stats,
+            teams: self.config.teams().cloned(),
+            team_scores: self.team_scores.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GameConfig, Judgment, RandomDriver, Scoring, ScoringRule, Transition};
+
+    use super::{GameResult, PlayerStats, Standing};
+
+    #[test]
+    fn test_ranking_with_ties() {
+        let result = |scores: &[i64]| {
+            let mut game = Judgment::new(scores.len() as u8, 1, None);
+            game.stage = crate::Stage::Over;
+            game.scores = scores.to_vec();
+            game.result().unwrap()
+        };
+        let ranks = |result: &GameResult| {
+            result
+                .ranking
+                .iter()
+                .map(|standing| (standing.rank, standing.player))
+                .collect::<Vec<_>>()
+        };
+        let tied = result(&[3, 5, 3, 5]);
+        assert_eq!(ranks(&tied), [(1, 1), (1, 3), (3, 0), (3, 2)]);
+        assert_eq!(tied.winners(), [1, 3]);
+        let distinct = result(&[-2, 4, 1]);
+        assert_eq!(ranks(&distinct), [(1, 1), (2, 2), (3, 0)]);
+        assert_eq!(
+            distinct.ranking[0],
+            Standing {
+                rank: 1,
+                player: 1,
+                score: 4
+            }
+        );
+        assert_eq!(distinct.winners(), [1]);
+    }
+
+    #[test]
+    fn test_result_of_played_game() {
+        let mut driver = RandomDriver::new(23);
+        let config = GameConfig::builder(3, 4).build().unwrap();
+        let mut game = Judgment::with_config(config.clone());
+        game.start().unwrap();
+        while !game.is_over() {
+            assert!(game.result().is_none());
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        let result = game.result().unwrap();
+        assert_eq!(result.rounds.len(), config.rounds());
+        for (round, hand_size) in result.rounds.iter().zip(config.hand_sizes()) {
+            assert_eq!(round.hand_size, hand_size);
+            assert_eq!(round.tricks.iter().sum::<u8>(), hand_size);
+            assert_eq!(
+                round.deltas,
+                config.scoring().settle(&round.predictions, &round.tricks)
+            );
+        }
+        for player in 0..3 {
+            let total: i64 = result.rounds.iter().map(|round| round.deltas[player]).sum();
+            assert_eq!(total, game.scores()[player]);
+        }
+        assert_eq!(*config.scoring(), Scoring::Standard);
+        for (player, stats) in result.stats.iter().enumerate() {
+            // with the standard rule, only correct predictions gain points
+            let hits = result
+                .rounds
+                .iter()
+                .filter(|round| round.deltas[player] > 0)
+                .count();
+            assert_eq!(*stats, PlayerStats { hits, rounds: 4 });
+        }
+        // replaying restores the breakdown as well
+        let replayed = Judgment::replay(config, game.history()).unwrap();
+        assert_eq!(replayed.result(), Some(result));
+    }
+
+    #[test]
+    fn test_hit_rate() {
+        assert_eq!(PlayerStats { hits: 0, rounds: 0 }.hit_rate(), 0.0);
+        assert_eq!(PlayerStats { hits: 3, rounds: 4 }.hit_rate(), 0.75);
+        let mut game = Judgment::new(2, 1, None);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [4; 32] }).unwrap();
+        for player in 0..2 {
+            game.update(Transition::PredictScore { player, score: 0 })
+                .unwrap();
+        }
+        for _ in 0..2 {
+            let player = game.current_player().unwrap();
+            let card = game.legal_cards(player)[0];
+            game.update(Transition::Play { player, card }).unwrap();
+        }
+        let result = game.result().unwrap();
+        let hit_rates: Vec<f64> = result.stats.iter().map(PlayerStats::hit_rate).collect();
+        // exactly one of the two players wins the only trick
+        assert!(hit_rates == [0.0, 1.0] || hit_rates == [1.0, 0.0]);
+    }
+}
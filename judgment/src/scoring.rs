@@ -0,0 +1,276 @@
+use std::{fmt::Debug, sync::Arc};
+
+/// Turns the predictions and trick counts of a finished round into points.
+///
+/// The engine calls [`ScoringRule::settle`] once at the end of every round
+/// and adds the returned points to the game scores.
+pub trait ScoringRule: Debug {
+    /// Points for a player who predicted `prediction` tricks and won `tricks`.
+    fn score(&self, prediction: u8, tricks: u8) -> i64;
+
+    /// Points for every player of a round, indexed like `predictions` and
+    /// `tricks`.
+    ///
+    /// By default every player is scored independently with
+    /// [`ScoringRule::score`].
+    fn settle(&self, predictions: &[u8], tricks: &[u8]) -> Vec<i64> {
+        predictions
+            .iter()
+            .zip(tricks)
+            .map(|(prediction, tricks)| self.score(*prediction, *tricks))
+            .collect()
+    }
+}
+
+/// Gain the prediction when it is exactly met, lose it otherwise. A prediction
+/// of zero counts as one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExactOrLose;
+
+impl ScoringRule for ExactOrLose {
+    fn score(&self, prediction: u8, tricks: u8) -> i64 {
+        let points = i64::from(prediction).max(1);
+        if prediction == tricks {
+            points
+        } else {
+            -points
+        }
+    }
+}
+
+/// Gain 10 plus the prediction when it is exactly met, nothing otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TenPlusBid;
+
+impl ScoringRule for TenPlusBid {
+    fn score(&self, prediction: u8, tricks: u8) -> i64 {
+        if prediction == tricks {
+            10 + i64::from(prediction)
+        } else {
+            0
+        }
+    }
+}
+
+/// Gain `hit_bonus` plus the prediction when it is exactly met, lose
+/// `penalty` for every trick the prediction was off by otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PenaltyPerTrick {
+    pub hit_bonus: i64,
+    pub penalty: i64,
+}
+
+impl ScoringRule for PenaltyPerTrick {
+    fn score(&self, prediction: u8, tricks: u8) -> i64 {
+        if prediction == tricks {
+            self.hit_bonus + i64::from(prediction)
+        } else {
+            -self.penalty * i64::from(prediction.abs_diff(tricks))
+        }
+    }
+}
+
+/// Same as [`ExactOrLose`], except that successfully predicting zero tricks
+/// gains `bonus` points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroBidBonus {
+    pub bonus: i64,
+}
+
+impl ScoringRule for ZeroBidBonus {
+    fn score(&self, prediction: u8, tricks: u8) -> i64 {
+        if prediction == 0 && tricks == 0 {
+            self.bonus
+        } else {
+            ExactOrLose.score(prediction, tricks)
+        }
+    }
+}
+
+/// The scoring rule of a game, as stored in its
+/// [`GameConfig`](crate::GameConfig).
+///
+/// Every built-in rule has a variant here; any other [`ScoringRule`] can be
+/// plugged in with [`Scoring::Custom`], at the cost of the config no longer
+/// being serializable.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scoring {
+    /// See [`ExactOrLose`].
+    #[default]
+    Standard,
+    /// See [`TenPlusBid`].
+    TenPlusBid,
+    /// See [`PenaltyPerTrick`].
+    PenaltyPerTrick { hit_bonus: i64, penalty: i64 },
+    /// See [`ZeroBidBonus`].
+    ZeroBidBonus { bonus: i64 },
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn ScoringRule + Send + Sync>),
+}
+
+impl ScoringRule for Scoring {
+    fn score(&self, prediction: u8, tricks: u8) -> i64 {
+        match self {
+            Scoring::Standard => ExactOrLose.score(prediction, tricks),
+            Scoring::TenPlusBid => TenPlusBid.score(prediction, tricks),
+            Scoring::PenaltyPerTrick { hit_bonus, penalty } => PenaltyPerTrick {
+                hit_bonus: *hit_bonus,
+                penalty: *penalty,
+            }
+            .score(prediction, tricks),
+            Scoring::ZeroBidBonus { bonus } => {
+                ZeroBidBonus { bonus: *bonus }.score(prediction, tricks)
+            }
+            Scoring::Custom(rule) => rule.score(prediction, tricks),
+        }
+    }
+
+    fn settle(&self, predictions: &[u8], tricks: &[u8]) -> Vec<i64> {
+        match self {
+            // custom rules may score the round as a whole
+            Scoring::Custom(rule) => rule.settle(predictions, tricks),
+            _ => predictions
+                .iter()
+                .zip(tricks)
+                .map(|(prediction, tricks)| self.score(*prediction, *tricks))
+                .collect(),
+        }
+    }
+}
+
+impl PartialEq for Scoring {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Scoring::Standard, Scoring::Standard) | (Scoring::TenPlusBid, Scoring::TenPlusBid) => {
+                true
+            }
+            (
+                Scoring::PenaltyPerTrick { hit_bonus, penalty },
+                Scoring::PenaltyPerTrick {
+                    hit_bonus: other_hit_bonus,
+                    penalty: other_penalty,
+                },
+            ) => hit_bonus == other_hit_bonus && penalty == other_penalty,
+            (Scoring::ZeroBidBonus { bonus }, Scoring::ZeroBidBonus { bonus: other_bonus }) => {
+                bonus == other_bonus
+            }
+            (Scoring::Custom(rule), Scoring::Custom(other_rule)) => Arc::ptr_eq(rule, other_rule),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Scoring {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{GameConfig, Judgment, Transition};
+
+    use super::{ExactOrLose, PenaltyPerTrick, Scoring, ScoringRule, TenPlusBid, ZeroBidBonus};
+
+    #[test]
+    fn test_exact_or_lose() {
+        assert_eq!(ExactOrLose.score(3, 3), 3);
+        assert_eq!(ExactOrLose.score(3, 2), -3);
+        assert_eq!(ExactOrLose.score(0, 0), 1);
+        assert_eq!(ExactOrLose.score(0, 1), -1);
+    }
+
+    #[test]
+    fn test_ten_plus_bid() {
+        assert_eq!(TenPlusBid.score(3, 3), 13);
+        assert_eq!(TenPlusBid.score(3, 4), 0);
+        assert_eq!(TenPlusBid.score(0, 0), 10);
+    }
+
+    #[test]
+    fn test_penalty_per_trick() {
+        let rule = PenaltyPerTrick {
+            hit_bonus: 5,
+            penalty: 2,
+        };
+        assert_eq!(rule.score(2, 2), 7);
+        assert_eq!(rule.score(2, 5), -6);
+        assert_eq!(rule.score(4, 1), -6);
+        assert_eq!(rule.score(0, 0), 5);
+    }
+
+    #[test]
+    fn test_zero_bid_bonus() {
+        let rule = ZeroBidBonus { bonus: 5 };
+        assert_eq!(rule.score(0, 0), 5);
+        assert_eq!(rule.score(0, 2), -1);
+        assert_eq!(rule.score(2, 2), 2);
+        assert_eq!(rule.score(2, 0), -2);
+    }
+
+    #[test]
+    fn test_config_scoring_matches_rules() {
+        let pairs = [(0, 0), (0, 1), (2, 2), (3, 1), (1, 3)];
+        let rules: [(Scoring, Box<dyn ScoringRule>); 4] = [
+            (Scoring::Standard, Box::new(ExactOrLose)),
+            (Scoring::TenPlusBid, Box::new(TenPlusBid)),
+            (
+                Scoring::PenaltyPerTrick {
+                    hit_bonus: 10,
+                    penalty: 1,
+                },
+                Box::new(PenaltyPerTrick {
+                    hit_bonus: 10,
+                    penalty: 1,
+                }),
+            ),
+            (
+                Scoring::ZeroBidBonus { bonus: 3 },
+                Box::new(ZeroBidBonus { bonus: 3 }),
+            ),
+        ];
+        for (scoring, rule) in rules {
+            for (prediction, tricks) in pairs {
+                assert_eq!(
+                    scoring.score(prediction, tricks),
+                    rule.score(prediction, tricks),
+                    "{scoring:?} disagrees with {rule:?}"
+                );
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct EveryoneGetsTheTotal;
+
+    impl ScoringRule for EveryoneGetsTheTotal {
+        fn score(&self, _prediction: u8, tricks: u8) -> i64 {
+            i64::from(tricks)
+        }
+
+        fn settle(&self, _predictions: &[u8], tricks: &[u8]) -> Vec<i64> {
+            let total = tricks.iter().map(|tricks| i64::from(*tricks)).sum();
+            vec![total; tricks.len()]
+        }
+    }
+
+    #[test]
+    fn test_custom_rule_settles_rounds() {
+        let config = GameConfig::builder(2, 1)
+            .scoring(Scoring::Custom(Arc::new(EveryoneGetsTheTotal)))
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [1; 32] }).unwrap();
+        for player in 0..2 {
+            game.update(Transition::PredictScore { player, score: 0 })
+                .unwrap();
+        }
+        for player in 0..2 {
+            let card = game.hand_of_player(player).unwrap()[0];
+            game.update(Transition::Play { player, card }).unwrap();
+        }
+        assert!(game.is_over());
+        assert_eq!(game.scores(), [1, 1]);
+    }
+}
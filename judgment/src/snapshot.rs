@@ -0,0 +1,271 @@
+use serde_json::Value;
+
+use crate::{Judgment, SnapshotError};
+
+/// The schema version of the snapshots written by this version of the crate.
+///
+/// It is bumped whenever the saved state changes shape, together with a new
+/// entry in [`MIGRATIONS`] that upgrades snapshots of the previous version.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// Upgrades the saved game of a snapshot by one schema version, in place.
+type Migration = fn(&mut Value) -> Result<(), SnapshotError>;
+
+/// `MIGRATIONS[i]` upgrades a game saved with schema version `i + 1` to
+/// version `i + 2`, so there is always one entry less than
+/// [`SNAPSHOT_VERSION`].
+const MIGRATIONS: [Migration; SNAPSHOT_VERSION as usize - 1] = [add_claim_rule];
+
+/// Version 2 added the rule for settling claims, which games saved before
+/// always played with the default.
+fn add_claim_rule(game: &mut Value) -> Result<(), SnapshotError> {
+    let config = game
+        .get_mut("config")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| SnapshotError::Malformed("missing field `config`".to_string()))?;
+    config.insert("claims".to_string(), Value::from("Agreement"));
+    Ok(())
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Snapshot<G> {
+    version: u32,
+    game: G,
+}
+
+impl Judgment {
+    /// Save the complete state of the game as JSON, including every hand,
+    /// the history of transitions and the rules it is played with.
+    ///
+    /// # Errors
+    /// Errors if the game is played with [`Scoring::Custom`](crate::Scoring::Custom),
+    /// which cannot be saved.
+    ///
+    /// # Examples
+    /// ```
+    /// use judgment::{Judgment, Transition};
+    ///
+    /// let mut game = Judgment::new(3, 5, None);
+    /// game.start().unwrap();
+    /// game.update(Transition::Deal { seed: [7; 32] }).unwrap();
+    /// let json = game.to_json().unwrap();
+    /// let restored = Judgment::from_json(&json).unwrap();
+    /// assert_eq!(restored.hand_of_player(1), game.hand_of_player(1));
+    /// assert_eq!(restored.history(), game.history());
+    /// ```
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string(&self.snapshot())
+            .map_err(|err| SnapshotError::Unserializable(err.to_string()))
+    }
+
+    /// Load a game saved with [`Judgment::to_json`] by this or an older
+    /// version of the crate.
+    ///
+    /// # Errors
+    /// Errors if the `json` is not a snapshot, or one written by a newer
+    /// version of the crate.
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let value =
+            serde_json::from_str(json).map_err(|err| SnapshotError::Malformed(err.to_string()))?;
+        Judgment::from_snapshot(value)
+    }
+
+    /// Save the complete state of the game like [`Judgment::to_json`], but
+    /// in the more compact MessagePack encoding.
+    ///
+    /// # Errors
+    /// Errors if the game is played with [`Scoring::Custom`](crate::Scoring::Custom),
+    /// which cannot be saved.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        rmp_serde::to_vec_named(&self.snapshot())
+            .map_err(|err| SnapshotError::Unserializable(err.to_string()))
+    }
+
+    /// Load a game saved with [`Judgment::to_bytes`] by this or an older
+    /// version of the crate.
+    ///
+    /// # Errors
+    /// Errors if the `bytes` are not a snapshot, or one written by a newer
+    /// version of the crate.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let value = rmp_serde::from_slice(bytes)
+            .map_err(|err| SnapshotError::Malformed(err.to_string()))?;
+        Judgment::from_snapshot(value)
+    }
+
+    fn snapshot(&self) -> Snapshot<&Judgment> {
+        Snapshot {
+            version: SNAPSHOT_VERSION,
+            game: self,
+        }
+    }
+
+    fn from_snapshot(value: Value) -> Result<Self, SnapshotError> {
+        let snapshot: Snapshot<Judgment> = serde_json::from_value(upgrade(value, &MIGRATIONS)?)
+            .map_err(|err| SnapshotError::Malformed(err.to_string()))?;
+        Ok(snapshot.game)
+    }
+}
+
+/// Bring a snapshot of any supported schema version up to the version after
+/// the last of the `migrations`.
+fn upgrade(mut snapshot: Value, migrations: &[Migration]) -> Result<Value, SnapshotError> {
+    let current = migrations.len() as u32 + 1;
+    let version = snapshot
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or(SnapshotError::MissingVersion)?;
+    if version == 0 || version > u64::from(current) {
+        return Err(SnapshotError::UnsupportedVersion {
+            version,
+            supported: current,
+        });
+    }
+    let game = snapshot
+        .get_mut("game")
+        .ok_or_else(|| SnapshotError::Malformed("missing field `game`".to_string()))?;
+    for migration in &migrations[version as usize - 1..] {
+        migration(game)?;
+    }
+    snapshot["version"] = current.into();
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::{json, Value};
+
+    use crate::{
+        ExactOrLose, GameConfig, Judgment, RandomDriver, Scoring, SnapshotError, SNAPSHOT_VERSION,
+    };
+
+    use super::{upgrade, Migration};
+
+    /// Keep playing both games with the same moves and check they agree.
+    fn assert_same_game(mut original: Judgment, mut restored: Judgment) {
+        let mut driver = RandomDriver::new(5);
+        while !original.is_over() {
+            assert_eq!(restored.to_json(), original.to_json());
+            let transition = driver.next_transition(&original).unwrap();
+            assert_eq!(restored.update(transition), original.update(transition));
+        }
+        assert_eq!(restored.result(), original.result());
+    }
+
+    #[test]
+    fn test_snapshots_round_trip() {
+        let config = GameConfig::builder(4, 5)
+            .decks(2)
+            .jokers(1)
+            .build()
+            .unwrap();
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        let mut driver = RandomDriver::new(31);
+        // stop in the middle of a trick of the second round
+        while game.round_results.is_empty() || game.first_of_trick.is_none() {
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        let from_json = Judgment::from_json(&game.to_json().unwrap()).unwrap();
+        assert_eq!(from_json.first_of_trick, game.first_of_trick);
+        assert_eq!(from_json.history, game.history);
+        assert_eq!(from_json.config.decks(), 2);
+        assert_eq!(from_json.stage, game.stage);
+        let bytes = game.to_bytes().unwrap();
+        assert!(bytes.len() < game.to_json().unwrap().len());
+        let from_bytes = Judgment::from_bytes(&bytes).unwrap();
+        assert_eq!(from_bytes.players, game.players);
+        assert_same_game(game.clone(), from_json);
+        assert_same_game(game, from_bytes);
+    }
+
+    #[test]
+    fn test_snapshot_versions() {
+        let game = Judgment::new(3, 2, None);
+        let mut snapshot: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        assert_eq!(snapshot["version"], SNAPSHOT_VERSION);
+        snapshot["version"] = json!(SNAPSHOT_VERSION + 1);
+        assert_eq!(
+            Judgment::from_json(&snapshot.to_string()).unwrap_err(),
+            SnapshotError::UnsupportedVersion {
+                version: u64::from(SNAPSHOT_VERSION) + 1,
+                supported: SNAPSHOT_VERSION
+            }
+        );
+        snapshot.as_object_mut().unwrap().remove("version");
+        assert_eq!(
+            Judgment::from_json(&snapshot.to_string()).unwrap_err(),
+            SnapshotError::MissingVersion
+        );
+        let bytes = game.to_bytes().unwrap();
+        assert!(matches!(
+            Judgment::from_bytes(&bytes[..bytes.len() / 2]),
+            Err(SnapshotError::Malformed(_))
+        ));
+        let custom = GameConfig::builder(3, 2)
+            .scoring(Scoring::Custom(Arc::new(ExactOrLose)))
+            .build()
+            .unwrap();
+        let game = Judgment::with_config(custom);
+        assert!(matches!(
+            game.to_json(),
+            Err(SnapshotError::Unserializable(_))
+        ));
+        assert!(matches!(
+            game.to_bytes(),
+            Err(SnapshotError::Unserializable(_))
+        ));
+    }
+
+    #[test]
+    fn test_version_1_snapshots_load() {
+        let mut game = Judgment::new(3, 2, None);
+        game.start().unwrap();
+        let mut snapshot: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        snapshot["game"]["config"]
+            .as_object_mut()
+            .unwrap()
+            .remove("claims");
+        snapshot["version"] = json!(1);
+        let restored = Judgment::from_json(&snapshot.to_string()).unwrap();
+        assert_eq!(restored.to_json(), game.to_json());
+        let bytes = rmp_serde::to_vec_named(&snapshot).unwrap();
+        let restored = Judgment::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.config, game.config);
+    }
+
+    #[test]
+    fn test_migrations_upgrade_old_snapshots() {
+        // pretend version 1 called the scores `points` and version 2 did not
+        // save the history yet
+        let rename: Migration = |game| {
+            let game = game.as_object_mut().unwrap();
+            let points = game.remove("points").unwrap();
+            game.insert("scores".to_string(), points);
+            Ok(())
+        };
+        let add_history: Migration = |game| {
+            game["history"] = json!([]);
+            Ok(())
+        };
+        let mut game = Judgment::new(2, 3, None);
+        game.start().unwrap();
+        let mut version_2: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        version_2["game"].as_object_mut().unwrap().remove("history");
+        version_2["version"] = json!(2);
+        let mut version_1 = version_2.clone();
+        let saved = version_1["game"].as_object_mut().unwrap();
+        let scores = saved.remove("scores").unwrap();
+        saved.insert("points".to_string(), scores);
+        version_1["version"] = json!(1);
+        for old in [version_1, version_2] {
+            let upgraded = upgrade(old, &[rename, add_history]).unwrap();
+            assert_eq!(upgraded["version"], 3);
+            let restored: Judgment = serde_json::from_value(upgraded["game"].clone()).unwrap();
+            assert_eq!(restored.to_json(), game.to_json());
+        }
+    }
+}
@@ -0,0 +1,1575 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasherDefault, Hasher},
+};
+
+use card_deck::standard_deck::{Card, Rank, Suit};
+
+use crate::{DuplicateCardRule, InvalidPosition, Judgment, Stage};
+
+/// A round in which every hand is known, ready to be analysed with perfect
+/// play.
+///
+/// Play follows the rules of the engine: the led suit must be followed when
+/// possible and trump beats every other suit, as decided by
+/// [`trick_winner`](crate::trick_winner). Of two identical cards the one
+/// played first wins, unless another [`DuplicateCardRule`] is set with
+/// [`DoubleDummy::with_duplicate_cards`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoubleDummy {
+    hands: Vec<Vec<Card>>,
+    trump_suit: Option<Suit>,
+    leader: usize,
+    trick: Vec<Option<Card>>,
+    duplicates: DuplicateCardRule,
+}
+
+impl DoubleDummy {
+    /// A position where the players hold `hands`, `leader` led the current
+    /// trick and `trick` holds the cards played to it so far, indexed by
+    /// player.
+    ///
+    /// # Errors
+    /// Errors if the trick does not fit the hands, see [`InvalidPosition`].
+    pub fn new(
+        hands: Vec<Vec<Card>>,
+        trump_suit: Option<Suit>,
+        leader: usize,
+        trick: Vec<Option<Card>>,
+    ) -> Result<Self, InvalidPosition> {
+        let players = hands.len();
+        if players == 0 || trick.len() != players {
+            return Err(InvalidPosition::PlayerCountMismatch);
+        }
+        if leader >= players {
+            return Err(InvalidPosition::LeaderOutOfRange);
+        }
+        // the cards of a trick are played in order starting from the leader
+        let played = trick.iter().filter(|card| card.is_some()).count();
+        if played == players
+            || (0..players)
+                .any(|offset| trick[(leader + offset) % players].is_some() != (offset < played))
+        {
+            return Err(InvalidPosition::TrickOutOfOrder);
+        }
+        // everyone who has played to the trick holds one card less
+        let tricks_left = hands[leader].len() + usize::from(played > 0);
+        for (seat, hand) in hands.iter().enumerate() {
+            let expected = tricks_left - usize::from(trick[seat].is_some());
+            if hand.len() != expected {
+                return Err(InvalidPosition::UnevenHands);
+            }
+        }
+        Ok(DoubleDummy {
+            hands,
+            trump_suit,
+            leader,
+            trick,
+            duplicates: DuplicateCardRule::FirstWins,
+        })
+    }
+
+    /// The same position, with identical cards in a trick settled by `rule`.
+    pub fn with_duplicate_cards(mut self, rule: DuplicateCardRule) -> Self {
+        self.duplicates = rule;
+        self
+    }
+
+    /// The most tricks `player` can take from here on, counting the current
+    /// trick, when everyone else plays to stop them.
+    ///
+    /// # Panics
+    /// Panics if there is no such player.
+    pub fn max_tricks(&self, player: usize) -> u8 {
+        assert!(player < self.hands.len(), "no such player");
+        let mut search = Search::new(self);
+        // with everyone else against them, players seldom take many tricks,
+        // so the targets are tried from the bottom up, the searches sharing
+        // what they learn about positions
+        (1..=search.tricks_left())
+            .find(|target| !search.can_take(player, *target).0)
+            .map_or(search.tricks_left(), |target| target - 1)
+    }
+
+    /// Whether `player` takes at least `tricks` tricks from here on,
+    /// counting the current trick, however everyone else plays.
+    ///
+    /// This is [`DoubleDummy::max_tricks`] for a single target, and much
+    /// quicker when `tricks` is near every trick left.
+    ///
+    /// # Panics
+    /// Panics if there is no such player.
+    pub fn can_take(&self, player: usize, tricks: u8) -> bool {
+        assert!(player < self.hands.len(), "no such player");
+        Search::new(self).can_take(player, tricks).0
+    }
+
+    /// Whether the round can be played out so that every player takes
+    /// exactly `tricks[player]` more tricks, counting the current one, if
+    /// all players work together.
+    ///
+    /// This answers whether all the predictions of a round can still be hit
+    /// when `tricks` is what every player still needs.
+    ///
+    /// # Panics
+    /// Panics if `tricks` does not have an entry for every player.
+    pub fn can_all_take(&self, tricks: &[u8]) -> bool {
+        assert_eq!(tricks.len(), self.hands.len(), "one entry per player");
+        let mut search = Search::new(self);
+        let mut needed = tricks.to_vec();
+        search.can_share(&mut needed).0
+    }
+}
+
+impl Judgment {
+    /// The current position with every hand revealed, or `None` if no cards
+    /// are being played. While a claim waits for an answer, it is the
+    /// position the claim was made in.
+    pub fn double_dummy(&self) -> Option<DoubleDummy> {
+        let (Stage::Play(round) | Stage::Claim(round, _)) = &self.stage else {
+            return None;
+        };
+        Some(DoubleDummy {
+            hands: self
+                .players
+                .iter()
+                .map(|player| player.hand().to_vec())
+                .collect(),
+            trump_suit: round.trump_suit,
+            leader: round.leader,
+            trick: self.trick.clone(),
+            duplicates: self.config.duplicate_cards(),
+        })
+    }
+}
+
+/// Jokers are kept as the only rank of a suit of their own, after the four
+/// real ones.
+const JOKER: usize = 4;
+const SUITS: usize = 5;
+
+/// A card as the index of its suit and rank, the lowest rank being 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CardIndex {
+    suit: usize,
+    rank: usize,
+}
+
+impl CardIndex {
+    fn of(card: &Card) -> Self {
+        let (Some(suit), Some(rank)) = (card.suit(), card.rank()) else {
+            return CardIndex {
+                suit: JOKER,
+                rank: 0,
+            };
+        };
+        let rank = match rank {
+            Rank::Numeric(value) => usize::from(*value) - 2,
+            Rank::Jack => 9,
+            Rank::Queen => 10,
+            Rank::King => 11,
+            Rank::Ace => 12,
+        };
+        CardIndex {
+            suit: suit_index(suit),
+            rank,
+        }
+    }
+}
+
+fn suit_index(suit: &Suit) -> usize {
+    match suit {
+        Suit::Clubs => 0,
+        Suit::Diamonds => 1,
+        Suit::Hearts => 2,
+        Suit::Spades => 3,
+    }
+}
+
+/// The cards of a player, laid out for quick lookups. Playing with more than
+/// one deck, a player may hold several copies of a card.
+#[derive(Debug, Clone)]
+struct Holding {
+    /// The copies held of every rank of every suit.
+    counts: [[u8; 13]; SUITS],
+    /// The ranks of every suit of which at least one copy is held, as bits.
+    ranks: [u16; SUITS],
+    /// The number of cards held in every suit.
+    lengths: [u8; SUITS],
+    len: usize,
+}
+
+impl Holding {
+    fn new(cards: &[Card]) -> Self {
+        let mut holding = Holding {
+            counts: [[0; 13]; SUITS],
+            ranks: [0; SUITS],
+            lengths: [0; SUITS],
+            len: 0,
+        };
+        for card in cards {
+            holding.add(CardIndex::of(card));
+        }
+        holding
+    }
+
+    fn add(&mut self, card: CardIndex) {
+        self.counts[card.suit][card.rank] += 1;
+        self.ranks[card.suit] |= 1 << card.rank;
+        self.lengths[card.suit] += 1;
+        self.len += 1;
+    }
+
+    fn remove(&mut self, card: CardIndex) {
+        self.counts[card.suit][card.rank] -= 1;
+        if self.counts[card.suit][card.rank] == 0 {
+            self.ranks[card.suit] &= !(1 << card.rank);
+        }
+        self.lengths[card.suit] -= 1;
+        self.len -= 1;
+    }
+}
+
+/// Hashes position keys. The standard hasher resists crafted collisions,
+/// which the search has no use for, at a cost that adds up over millions of
+/// lookups.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.0 = (self.0.rotate_left(5) ^ u64::from_le_bytes(word))
+                .wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+        }
+    }
+}
+
+/// The ranks set in `bits`, from low to high.
+fn ranks_in(bits: u16) -> impl DoubleEndedIterator<Item = usize> {
+    RankBits(bits)
+}
+
+/// Iterates over the set bits of a rank set, skipping those that are not.
+struct RankBits(u16);
+
+impl Iterator for RankBits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let rank = (self.0 != 0).then(|| self.0.trailing_zeros() as usize)?;
+        self.0 &= self.0 - 1;
+        Some(rank)
+    }
+}
+
+impl DoubleEndedIterator for RankBits {
+    fn next_back(&mut self) -> Option<usize> {
+        let rank = (self.0 != 0).then(|| 15 - self.0.leading_zeros() as usize)?;
+        self.0 &= !(1 << rank);
+        Some(rank)
+    }
+}
+
+/// The ranks from `low` to `high`, both included, as bits.
+fn rank_range(low: usize, high: usize) -> u16 {
+    ((1 << (high + 1)) - 1) & !((1 << low) - 1)
+}
+
+/// The mutable state of a search, which plays and takes back cards on a
+/// single copy of the position.
+struct Search {
+    hands: Vec<Holding>,
+    trump_suit: Option<usize>,
+    duplicates: DuplicateCardRule,
+    leader: usize,
+    player: usize,
+    /// Every card played so far with its player, the last `played` of them
+    /// making up the current trick.
+    plays: Vec<(usize, CardIndex)>,
+    played: usize,
+    /// What is known about positions at the start of a trick.
+    known: Table<Bounds>,
+    /// Positions at the start of a trick, together with the tricks everyone
+    /// still needed, from which the needs cannot all be met.
+    dead_ends: Table<()>,
+    /// The positions at the start of the current trick and of every trick
+    /// before it, as described by [`Search::describe`], indexed by the
+    /// tricks left.
+    descriptions: Vec<Description>,
+}
+
+/// The tricks the maximizing player can take from a position, as far as it
+/// has been searched.
+struct Bounds {
+    low: u8,
+    high: u8,
+    /// The card that settled the last search from the position.
+    best: Option<CardIndex>,
+}
+
+/// What is needed to take back a card.
+struct Played {
+    seat: usize,
+    card: CardIndex,
+    /// The leader of the trick, if the card completed it.
+    completed: Option<usize>,
+    /// What the winner of the trick depends on, if the card completed it.
+    decided_by: Relevance,
+}
+
+/// What the result of a search depends on: for every suit, the lowest rank
+/// whose holder made a difference. Of the cards below it, only how many of
+/// them every player held mattered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Relevance([u8; SUITS]);
+
+impl Relevance {
+    /// Only the number of cards everyone holds in every suit matters.
+    const LENGTHS: Relevance = Relevance([13; SUITS]);
+    const ALL: Relevance = Relevance([0; SUITS]);
+
+    /// Everything that either `self` or `other` depends on.
+    fn and(mut self, other: Relevance) -> Relevance {
+        for (rank, other) in self.0.iter_mut().zip(other.0) {
+            *rank = (*rank).min(other);
+        }
+        self
+    }
+
+    /// Everything `self` depends on, as well as `card` and every card
+    /// above it.
+    fn and_card(mut self, card: CardIndex) -> Relevance {
+        self.0[card.suit] = self.0[card.suit].min(card.rank as u8);
+        self
+    }
+
+    /// Used as the ranks of every suit below which the moves are known to
+    /// make no difference: those known so far, along with the cards ranking
+    /// below `card` if playing it led to a result that depends on
+    /// `relevance` and not on `card` itself.
+    fn or_below(mut self, card: CardIndex, relevance: Relevance) -> Relevance {
+        let lowest = relevance.0[card.suit];
+        if (card.rank as u8) < lowest {
+            self.0[card.suit] = self.0[card.suit].max(lowest);
+        }
+        self
+    }
+
+    /// Whether `card` ranks below what [`Relevance::or_below`] collected, so
+    /// that playing it makes no difference to a move tried before.
+    fn covers(self, card: CardIndex) -> bool {
+        (card.rank as u8) < self.0[card.suit]
+    }
+}
+
+/// A position at the start of a trick, in the terms of a [`Table`].
+#[derive(Default)]
+struct Description {
+    tricks_left: usize,
+    /// The leader and the length of every suit of every player, followed by
+    /// anything else a result depends on.
+    group: Vec<u8>,
+    /// Who holds the cards of every suit, from the highest card down. The
+    /// first holder of every rank is marked with [`NEW_RANK`].
+    holders: Vec<u16>,
+    /// The rank of every card of `holders`.
+    ranks: Vec<u8>,
+    /// Where the cards of every suit start in `holders`, and where the last
+    /// one ends.
+    suit_starts: [usize; SUITS + 1],
+    /// A hash of the top cards of every suit for every number of them that
+    /// ends with a whole rank, [`NO_CUT`] for the others. The hashes of a
+    /// suit start at its start in `holders` plus the number of the suit.
+    prefixes: Vec<u64>,
+}
+
+/// The marker of the first holder of a rank in [`Description::holders`].
+const NEW_RANK: u16 = 0x100;
+
+/// The prefix hash of a number of cards that splits a rank.
+const NO_CUT: u64 = u64::MAX;
+
+/// How many of the top cards of every suit a result depends on.
+type Cuts = [u8; SUITS];
+
+impl Description {
+    /// The number of top cards of every suit that ranks at least as high as
+    /// `relevance` asks for.
+    fn cuts(&self, relevance: Relevance) -> Cuts {
+        let mut cuts = [0; SUITS];
+        for (suit, cut) in cuts.iter_mut().enumerate() {
+            let ranks = &self.ranks[self.suit_starts[suit]..self.suit_starts[suit + 1]];
+            *cut = ranks
+                .iter()
+                .take_while(|rank| **rank >= relevance.0[suit])
+                .count() as u8;
+        }
+        cuts
+    }
+
+    /// A hash of the top cards given by `cuts`, or `None` if they split a
+    /// rank. The cuts must fit the suit lengths of the group.
+    fn hash(&self, cuts: &Cuts) -> Option<u64> {
+        let mut hash = 0u64;
+        for (suit, cut) in cuts.iter().enumerate() {
+            let prefix = self.prefixes[self.suit_starts[suit] + suit + usize::from(*cut)];
+            if prefix == NO_CUT {
+                return None;
+            }
+            hash = hash.rotate_left(13) ^ prefix;
+        }
+        Some(hash)
+    }
+
+    /// Add the holders of the top cards given by `cuts` to `holders`, suit
+    /// after suit.
+    fn extend_holders(&self, cuts: &Cuts, holders: &mut Vec<u16>) {
+        for (suit, cut) in cuts.iter().enumerate() {
+            let start = self.suit_starts[suit];
+            holders.extend_from_slice(&self.holders[start..start + usize::from(*cut)]);
+        }
+    }
+
+    /// Whether `holders` holds the top cards given by `cuts`.
+    fn holds(&self, cuts: &Cuts, holders: &[u16]) -> bool {
+        let mut holders = holders;
+        cuts.iter().enumerate().all(|(suit, cut)| {
+            let start = self.suit_starts[suit];
+            let (top, rest) = holders.split_at(usize::from(*cut));
+            holders = rest;
+            self.holders[start..start + top.len()] == *top
+        })
+    }
+
+    /// What a result depending on the top cards given by `cuts` depends on.
+    fn relevance(&self, cuts: &Cuts) -> Relevance {
+        let mut relevance = Relevance::LENGTHS;
+        for (suit, cut) in cuts.iter().enumerate() {
+            if *cut > 0 {
+                relevance.0[suit] = self.ranks[self.suit_starts[suit] + usize::from(*cut) - 1];
+            }
+        }
+        relevance
+    }
+}
+
+/// The most entries a [`Table`] holds. Once it is full, it forgets the
+/// positions with the fewest tricks left, which are the quickest to search
+/// again, until it is half empty. This bounds the memory a search takes.
+const TABLE_CAPACITY: usize = 1 << 19;
+
+/// Results for positions at the start of a trick. Every result is stored
+/// with only the cards it depends on, so that it also answers for positions
+/// that differ from its own in cards that did not matter.
+///
+/// Positions are grouped by the tricks left and [`Description::group`], and
+/// the entries of a group by how many top cards of every suit they depend
+/// on. An entry lists who holds those cards, along with a hash of them to
+/// rule out most entries that do not apply at a glance.
+struct Table<T> {
+    /// The groups with every number of tricks left, and how many entries
+    /// they hold.
+    levels: Vec<(Groups<T>, usize)>,
+    entries: usize,
+}
+
+type Groups<T> = HashMap<Vec<u8>, Vec<Shape<T>>, BuildHasherDefault<KeyHasher>>;
+
+/// The entries of a group that depend on the same number of top cards.
+struct Shape<T> {
+    cuts: Cuts,
+    /// The holders of the top cards of every entry, one entry after the
+    /// other.
+    holders: Vec<u16>,
+    /// The hash of the holders of every entry, and its result.
+    entries: Vec<(u64, T)>,
+}
+
+impl<T> Shape<T> {
+    /// The holders of the top cards of entry `index`.
+    fn holders(&self, index: usize) -> &[u16] {
+        let len = self.cuts.iter().map(|cut| usize::from(*cut)).sum::<usize>();
+        &self.holders[index * len..(index + 1) * len]
+    }
+}
+
+impl<T> Table<T> {
+    fn new() -> Self {
+        Table {
+            levels: Vec::new(),
+            entries: 0,
+        }
+    }
+
+    /// The results that apply to `position`, with what they depend on in it.
+    fn get<'a>(
+        &'a self,
+        position: &'a Description,
+    ) -> impl Iterator<Item = (&'a T, Relevance)> + 'a {
+        self.levels
+            .get(position.tricks_left)
+            .and_then(|(groups, _)| groups.get(&position.group))
+            .into_iter()
+            .flatten()
+            .filter_map(|shape| Some((shape, position.hash(&shape.cuts)?)))
+            .flat_map(move |(shape, hash)| {
+                shape
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .filter(move |(index, (known, _))| {
+                        *known == hash && position.holds(&shape.cuts, shape.holders(*index))
+                    })
+                    .map(|(_, (_, value))| (value, position.relevance(&shape.cuts)))
+            })
+    }
+
+    /// The result for `position` depending on `relevance`, inserted with
+    /// `default` if there is none yet.
+    fn entry(
+        &mut self,
+        position: &Description,
+        relevance: Relevance,
+        default: impl FnOnce() -> T,
+    ) -> &mut T {
+        if self.entries == TABLE_CAPACITY {
+            for (groups, entries) in &mut self.levels {
+                if self.entries <= TABLE_CAPACITY / 2 {
+                    break;
+                }
+                groups.clear();
+                self.entries -= *entries;
+                *entries = 0;
+            }
+        }
+        let cuts = position.cuts(relevance);
+        let hash = position
+            .hash(&cuts)
+            .expect("relevant cards end with a rank");
+        if self.levels.len() <= position.tricks_left {
+            self.levels
+                .resize_with(position.tricks_left + 1, || (HashMap::default(), 0));
+        }
+        let (groups, entries) = &mut self.levels[position.tricks_left];
+        let shapes = groups.entry(position.group.clone()).or_default();
+        let shape = match shapes.iter().position(|shape| shape.cuts == cuts) {
+            Some(shape) => shape,
+            None => {
+                shapes.push(Shape {
+                    cuts,
+                    holders: Vec::new(),
+                    entries: Vec::new(),
+                });
+                shapes.len() - 1
+            }
+        };
+        let shape = &mut shapes[shape];
+        let index = (0..shape.entries.len()).find(|index| {
+            shape.entries[*index].0 == hash && position.holds(&cuts, shape.holders(*index))
+        });
+        let index = match index {
+            Some(index) => index,
+            None => {
+                position.extend_holders(&cuts, &mut shape.holders);
+                shape.entries.push((hash, default()));
+                *entries += 1;
+                self.entries += 1;
+                shape.entries.len() - 1
+            }
+        };
+        &mut shape.entries[index].1
+    }
+}
+
+impl Search {
+    fn new(position: &DoubleDummy) -> Self {
+        let players = position.hands.len();
+        let plays: Vec<(usize, CardIndex)> = (0..players)
+            .map(|offset| (position.leader + offset) % players)
+            .filter_map(|seat| {
+                position.trick[seat]
+                    .as_ref()
+                    .map(|card| (seat, CardIndex::of(card)))
+            })
+            .collect();
+        let played = plays.len();
+        let tricks_left = position.hands[position.leader].len() + usize::from(played > 0);
+        Search {
+            hands: position
+                .hands
+                .iter()
+                .map(|hand| Holding::new(hand))
+                .collect(),
+            trump_suit: position.trump_suit.as_ref().map(suit_index),
+            duplicates: position.duplicates,
+            leader: position.leader,
+            player: (position.leader + played) % players,
+            plays,
+            played,
+            known: Table::new(),
+            dead_ends: Table::new(),
+            descriptions: (0..=tricks_left).map(|_| Description::default()).collect(),
+        }
+    }
+
+    fn players(&self) -> usize {
+        self.hands.len()
+    }
+
+    /// The hands of everyone but `seat`.
+    fn rivals(&self, seat: usize) -> impl Iterator<Item = &Holding> + Clone {
+        self.hands
+            .iter()
+            .enumerate()
+            .filter(move |(other, _)| *other != seat)
+            .map(|(_, hand)| hand)
+    }
+
+    fn tricks_left(&self) -> u8 {
+        (self.hands[self.leader].len + usize::from(self.played > 0)) as u8
+    }
+
+    /// The cards played to the current trick with their players, in the
+    /// order they were played.
+    fn trick_cards(&self) -> impl Iterator<Item = (usize, CardIndex)> + '_ {
+        self.plays[self.plays.len() - self.played..].iter().copied()
+    }
+
+    /// The suit to follow in the current trick, that of the first card that
+    /// is not a joker.
+    fn led_suit(&self) -> Option<usize> {
+        self.trick_cards()
+            .map(|(_, card)| card.suit)
+            .find(|suit| *suit != JOKER)
+    }
+
+    /// Whether `next`, played after `best`, takes the trick from it. Of two
+    /// equal cards the first one played wins, unless the last one does.
+    fn beats(&self, best: CardIndex, next: CardIndex) -> bool {
+        if next.suit == best.suit {
+            next.rank > best.rank
+                || (next.rank == best.rank && self.duplicates == DuplicateCardRule::LastWins)
+        } else {
+            next.suit == JOKER || (best.suit != JOKER && self.trump_suit == Some(next.suit))
+        }
+    }
+
+    /// Orders cards by how likely they are to win a trick.
+    fn strength(&self, card: &CardIndex) -> (bool, usize) {
+        match card.suit {
+            JOKER => (true, 13),
+            suit => (self.trump_suit == Some(suit), card.rank),
+        }
+    }
+
+    /// The seat and card winning the current trick, if any card was played.
+    fn winning(&self) -> Option<(usize, CardIndex)> {
+        let led = self.trick_cards().next()?;
+        let led_suit = self.led_suit();
+        let cancels = self.duplicates == DuplicateCardRule::Cancel;
+        let winner = self
+            .trick_cards()
+            .filter(|(_, card)| {
+                !cancels
+                    || (self
+                        .trick_cards()
+                        .filter(|(_, other)| other == card)
+                        .count()
+                        == 1
+                        && (card.suit == JOKER
+                            || led_suit == Some(card.suit)
+                            || self.trump_suit == Some(card.suit)))
+            })
+            .reduce(|best, next| {
+                if self.beats(best.1, next.1) {
+                    next
+                } else {
+                    best
+                }
+            });
+        // with every card cancelled, the leader takes the trick
+        Some(winner.unwrap_or(led))
+    }
+
+    /// Describe the position at the start of the current trick, and return
+    /// the description. Only the order of the cards still held matters, so
+    /// every suit is described by who holds the cards that are left from high
+    /// to low, without their ranks.
+    fn describe(&mut self) -> &mut Description {
+        let tricks_left = usize::from(self.tricks_left());
+        let description = &mut self.descriptions[tricks_left];
+        description.tricks_left = tricks_left;
+        description.group.clear();
+        description.group.push(self.leader as u8);
+        for hand in &self.hands {
+            description.group.extend_from_slice(&hand.lengths);
+        }
+        description.holders.clear();
+        description.ranks.clear();
+        description.prefixes.clear();
+        for suit in 0..SUITS {
+            let left = self
+                .hands
+                .iter()
+                .fold(0, |bits, hand| bits | hand.ranks[suit]);
+            let start = description.holders.len();
+            description.suit_starts[suit] = start;
+            for rank in ranks_in(left).rev() {
+                let mut mark = NEW_RANK;
+                for (seat, hand) in self.hands.iter().enumerate() {
+                    for _ in 0..hand.counts[suit][rank] {
+                        description.holders.push(seat as u16 | mark);
+                        description.ranks.push(rank as u8);
+                        mark = 0;
+                    }
+                }
+            }
+            let mut prefix = suit as u64;
+            for holder in &description.holders[start..] {
+                description.prefixes.push(if holder & NEW_RANK != 0 {
+                    prefix
+                } else {
+                    NO_CUT
+                });
+                prefix = (prefix ^ u64::from(*holder)).wrapping_mul(0x9e_37_79_b9_7f_4a_7c_15);
+            }
+            description.prefixes.push(prefix);
+        }
+        description.suit_starts[SUITS] = description.holders.len();
+        description
+    }
+
+    /// The cards the current player may play, leaving out all but one of
+    /// every group of cards that would make no difference to the outcome.
+    ///
+    /// Two cards of a suit are interchangeable when no card still in play
+    /// elsewhere ranks between them.
+    fn moves(&self) -> Vec<CardIndex> {
+        let hand = &self.hands[self.player];
+        let led_suit = self.led_suit().filter(|suit| hand.ranks[*suit] != 0);
+        // jokers may always be played
+        let suits =
+            (0..SUITS).filter(|suit| led_suit.is_none_or(|led| *suit == led || *suit == JOKER));
+        let mut moves = Vec::new();
+        for suit in suits {
+            let elsewhere = self
+                .hands
+                .iter()
+                .enumerate()
+                .filter(|(seat, _)| *seat != self.player)
+                .map(|(_, hand)| hand.ranks[suit])
+                .chain(
+                    self.trick_cards()
+                        .filter(|(_, card)| card.suit == suit)
+                        .map(|(_, card)| 1 << card.rank),
+                )
+                .fold(0, |bits, ranks| bits | ranks);
+            let mut last = None;
+            for rank in ranks_in(hand.ranks[suit]) {
+                if last.is_none_or(|last| elsewhere & rank_range(last, rank) != 0) {
+                    moves.push(CardIndex { suit, rank });
+                }
+                last = Some(rank);
+            }
+        }
+        moves
+    }
+
+    /// The moves of the current player, the most promising first for the
+    /// side that `player` is on.
+    fn ordered_moves(&self, player: usize) -> Vec<CardIndex> {
+        let mut moves = self.moves();
+        let strength = |card: &CardIndex| self.strength(card);
+        let highest = |bits: u16| 16 - bits.leading_zeros();
+        let hand = &self.hands[player];
+        let elsewhere = |suit: usize| {
+            self.hands
+                .iter()
+                .enumerate()
+                .filter(|(seat, _)| *seat != player)
+                .fold(0, |bits, (_, hand)| bits | hand.ranks[suit])
+        };
+        match self.winning() {
+            // leading: the player tries high cards first
+            None if self.player == player => {
+                moves.sort_by_key(|card| std::cmp::Reverse(strength(card)));
+            }
+            // their opponents lead low in suits the player cannot win
+            None => {
+                let can_win = |suit: usize| match hand.ranks[suit] {
+                    0 => self
+                        .trump_suit
+                        .is_some_and(|trump| trump != suit && hand.ranks[trump] != 0),
+                    ranks => highest(ranks) > highest(elsewhere(suit)),
+                };
+                moves.sort_by_key(|card| (can_win(card.suit), strength(card)));
+            }
+            Some((winner, best)) => {
+                let player_to_come = self.trick_cards().all(|(seat, _)| seat != player);
+                if self.player != player && player_to_come {
+                    // opponents playing before the player try to beat the
+                    // best card the player could answer with, as cheaply as
+                    // possible
+                    let answer = self
+                        .led_suit()
+                        .filter(|suit| hand.ranks[*suit] != 0)
+                        .or(self.trump_suit.filter(|trump| hand.ranks[*trump] != 0))
+                        .map(|suit| CardIndex {
+                            suit,
+                            rank: highest(hand.ranks[suit]) as usize - 1,
+                        });
+                    let stops = |card: &CardIndex| {
+                        self.beats(best, *card)
+                            && answer.is_none_or(|answer| !self.beats(*card, answer))
+                    };
+                    moves.sort_by_key(|card| (!stops(card), strength(card)));
+                } else {
+                    // cheapest winning cards first if the trick is wanted,
+                    // cheapest losing cards first otherwise
+                    let wants_to_win = self.player == player || winner == player;
+                    moves.sort_by_key(|card| {
+                        (self.beats(best, *card) != wants_to_win, strength(card))
+                    });
+                }
+            }
+        }
+        moves
+    }
+
+    /// The moves of the current player, those that hand the trick to the
+    /// players who still need the most tricks first.
+    fn sharing_moves(&self, needed: &[u8]) -> Vec<CardIndex> {
+        let mut moves = self.moves();
+        let winning = self.winning();
+        moves.sort_by_key(|card| {
+            let winner = match winning {
+                Some((winner, best)) if !self.beats(best, *card) => winner,
+                _ => self.player,
+            };
+            // a player who wants the trick plays their highest cards first
+            // to keep it, everyone else their lowest to stay out of the way
+            let (trump, rank) = self.strength(card);
+            let strength = (usize::from(trump) << 4 | rank) as isize;
+            let wanted = winner == self.player && needed[winner] > 0;
+            let strength = if wanted { -strength } else { strength };
+            (std::cmp::Reverse(needed[winner]), strength)
+        });
+        moves
+    }
+
+    fn play(&mut self, card: CardIndex) -> Played {
+        let seat = self.player;
+        self.hands[seat].remove(card);
+        self.plays.push((seat, card));
+        self.played += 1;
+        self.player = (seat + 1) % self.players();
+        let mut completed = None;
+        let mut decided_by = Relevance::LENGTHS;
+        if self.played == self.players() {
+            let (winner, winning_card) = self.winning().expect("a complete trick has a winner");
+            // the rank of the winning card only matters against other cards
+            // of its suit
+            if self.duplicates == DuplicateCardRule::Cancel {
+                decided_by = Relevance::ALL;
+            } else if self
+                .trick_cards()
+                .filter(|(_, card)| card.suit == winning_card.suit)
+                .nth(1)
+                .is_some()
+            {
+                decided_by = decided_by.and_card(winning_card);
+            }
+            completed = Some(self.leader);
+            self.leader = winner;
+            self.player = winner;
+            self.played = 0;
+        }
+        Played {
+            seat,
+            card,
+            completed,
+            decided_by,
+        }
+    }
+
+    fn take_back(&mut self, played: Played) {
+        if let Some(leader) = played.completed {
+            self.leader = leader;
+            self.played = self.players();
+        }
+        self.played -= 1;
+        self.plays.pop();
+        self.hands[played.seat].add(played.card);
+        self.player = played.seat;
+    }
+
+    /// The tricks the leader of a new trick is sure to take by leading cards
+    /// that nobody can beat, one after the other.
+    fn quick_tricks(&self) -> u8 {
+        let hand = &self.hands[self.leader];
+        // the copies of the top ranks of a suit held by the leader alone
+        let run = |suit: usize| {
+            let others = self
+                .rivals(self.leader)
+                .fold(0, |bits, hand| bits | hand.ranks[suit]);
+            let above = 16 - others.leading_zeros();
+            ranks_in(hand.ranks[suit] >> above << above)
+                .map(|rank| usize::from(hand.counts[suit][rank]))
+                .sum::<usize>()
+        };
+        let trump_suit = self.trump_suit;
+        let ruffs = |hand: &&Holding| trump_suit.is_some_and(|trump| hand.ranks[trump] != 0);
+        let tricks = if !self.rivals(self.leader).any(|hand| ruffs(&hand)) {
+            (0..4).map(run).sum()
+        } else {
+            // top trumps are safe to lead first, after which a side suit is
+            // safe for as long as everyone who could ruff it has to follow.
+            // Following one suit leaves their other suits as they were, so
+            // the side suits can be led one after the other.
+            let trump_run = trump_suit.map_or(0, run);
+            let side_runs = (0..4)
+                .filter(|suit| trump_suit != Some(*suit))
+                .map(|suit| {
+                    let followers = self
+                        .rivals(self.leader)
+                        .filter(ruffs)
+                        .map(|hand| usize::from(hand.lengths[suit]));
+                    run(suit).min(followers.min().unwrap_or(usize::MAX))
+                })
+                .sum::<usize>();
+            trump_run + side_runs
+        };
+        tricks.min(usize::from(self.tricks_left())) as u8
+    }
+
+    /// What [`Search::quick_tricks`] depends on: the leader's cards down to
+    /// the best card of their rivals in every suit.
+    fn quick_tricks_relevance(&self) -> Relevance {
+        let mut relevance = Relevance::LENGTHS;
+        for suit in 0..4 {
+            let others = self
+                .rivals(self.leader)
+                .fold(0, |bits, hand| bits | hand.ranks[suit]);
+            if let Some(rank) = ranks_in(others).next_back() {
+                relevance = relevance.and_card(CardIndex { suit, rank });
+            }
+        }
+        relevance
+    }
+
+    /// Whether the leader of a new trick holds a card `player` cannot beat,
+    /// and if so, what that depends on.
+    fn leads_over(&self, player: usize) -> Option<Relevance> {
+        let leader = &self.hands[self.leader];
+        let hand = &self.hands[player];
+        let highest = |bits: u16| ranks_in(bits).next_back();
+        (0..4)
+            .filter(|suit| leader.ranks[*suit] != 0)
+            .find_map(|suit| match highest(hand.ranks[suit]) {
+                // the player has to follow, and loses to an equal card unless
+                // the last one wins
+                Some(own) => {
+                    let top = highest(leader.ranks[suit]).unwrap_or_default();
+                    let loses = match self.duplicates {
+                        DuplicateCardRule::LastWins => own < top,
+                        _ => own <= top,
+                    };
+                    loses.then(|| Relevance::LENGTHS.and_card(CardIndex { suit, rank: own }))
+                }
+                // the player can only ruff, and loses to a higher trump
+                None => {
+                    let trumps = self.trump_suit.map_or(0, |trump| hand.ranks[trump]);
+                    (self.trump_suit == Some(suit) || trumps == 0).then_some(Relevance::LENGTHS)
+                }
+            })
+    }
+
+    /// The tricks `seat` is sure to take with trumps against the trumps of
+    /// `rivals`, counting no further than `enough`, along with what that
+    /// depends on. Every rival comes with the most of the seat's trumps they
+    /// may beat.
+    ///
+    /// A trump only loses to a higher or an equal trump, and each rival trump
+    /// can beat at most one of them. The count only looks at the trumps from
+    /// the highest rank that makes it enough, so the lower ones do not matter.
+    fn trump_tricks(
+        &self,
+        seat: usize,
+        rivals: impl Iterator<Item = (usize, u8)>,
+        enough: u8,
+    ) -> (u8, Relevance) {
+        let Some(trump) = self.trump_suit else {
+            return (0, Relevance::LENGTHS);
+        };
+        let held = self.hands[seat].counts[trump];
+        // a rival's highest trumps beat whatever their lower ones could
+        let mut beaters = [0; 13];
+        let mut ranks = self.hands[seat].ranks[trump];
+        for (rival, most) in rivals {
+            let hand = &self.hands[rival];
+            let mut left = most;
+            for rank in ranks_in(hand.ranks[trump]).rev() {
+                let count = hand.counts[trump][rank].min(left);
+                beaters[rank] += count;
+                left -= count;
+                ranks |= 1 << rank;
+            }
+        }
+        // any rival trump from a rank up can beat any of the seat's trumps
+        // of that rank
+        let mut relevance = Relevance::ALL;
+        let (mut unbeaten, mut spare) = (0, 0);
+        for rank in ranks_in(ranks).rev() {
+            spare += beaters[rank];
+            let beaten = held[rank].min(spare);
+            spare -= beaten;
+            unbeaten += held[rank] - beaten;
+            if unbeaten >= enough {
+                relevance = Relevance::LENGTHS;
+                relevance.0[trump] = rank as u8;
+                break;
+            }
+        }
+        (unbeaten, relevance)
+    }
+
+    /// Bounds on the tricks `player` can take from the start of a trick,
+    /// each along with what it depends on.
+    fn quick_bounds(&self, player: usize, target: u8) -> ((u8, Relevance), (u8, Relevance)) {
+        let tricks_left = self.tricks_left();
+        // the bounds assume that nothing beats the top trumps
+        if self.hands.iter().any(|hand| hand.lengths[JOKER] > 0) {
+            return ((0, Relevance::LENGTHS), (tricks_left, Relevance::LENGTHS));
+        }
+        let rivals = (0..self.players()).filter(|seat| *seat != player);
+        let unlimited = |seat| (seat, u8::MAX);
+        let mut low = self.trump_tricks(player, rivals.clone().map(unlimited), target);
+        // when identical cards cancel, a rival's card that would beat the
+        // player can be cancelled by a third one, so only cards nobody else
+        // holds are sure to win
+        let cancels = self.duplicates == DuplicateCardRule::Cancel;
+        let mut lost = rivals
+            .filter(|_| !cancels)
+            .map(|seat| {
+                let rivals = std::iter::once(unlimited(player));
+                self.trump_tricks(seat, rivals, tricks_left + 1 - target)
+            })
+            .max_by_key(|(tricks, _)| *tricks)
+            .unwrap_or((0, Relevance::LENGTHS));
+        let quick = self.quick_tricks();
+        if self.leader == player && quick > low.0 {
+            low = (quick, self.quick_tricks_relevance());
+        } else if self.leader != player && quick > lost.0 {
+            lost = (quick, self.quick_tricks_relevance());
+        }
+        if self.leader != player && lost.0 == 0 && !cancels {
+            if let Some(relevance) = self.leads_over(player) {
+                lost = (1, relevance);
+            }
+        }
+        (
+            (low.0.min(tricks_left), low.1),
+            (tricks_left - lost.0.min(tricks_left), lost.1),
+        )
+    }
+
+    /// Whether `player` can take at least `target` tricks from here on
+    /// against everyone else, and what that depends on.
+    fn can_take(&mut self, player: usize, target: u8) -> (bool, Relevance) {
+        if target == 0 {
+            return (true, Relevance::LENGTHS);
+        }
+        let tricks_left = self.tricks_left();
+        if target > tricks_left {
+            return (false, Relevance::LENGTHS);
+        }
+        let trick_start = self.played == 0;
+        let mut hint = None;
+        if trick_start {
+            let ((low, sure), (high, capped)) = self.quick_bounds(player, target);
+            if low >= target {
+                return (true, sure);
+            }
+            if high < target {
+                return (false, capped);
+            }
+            self.describe();
+            let description = &self.descriptions[usize::from(tricks_left)];
+            for (known, relevance) in self.known.get(description) {
+                if known.low >= target {
+                    return (true, relevance);
+                }
+                if known.high < target {
+                    return (false, relevance);
+                }
+                hint = hint.or(known.best);
+            }
+        }
+        let maximizing = self.player == player;
+        // the maximizing player needs one good move, the others need every
+        // move to fail
+        let mut result = !maximizing;
+        let mut relevance = Relevance::LENGTHS;
+        let mut best = None;
+        let mut moves = self.ordered_moves(player);
+        if let Some(position) = hint.and_then(|hint| moves.iter().position(|card| *card == hint)) {
+            moves[..=position].rotate_right(1);
+        }
+        let mut settled = Relevance::ALL;
+        for card in moves {
+            if settled.covers(card) {
+                continue;
+            }
+            let played = self.play(card);
+            let won = played.completed.is_some() && self.leader == player;
+            let (reachable, depends) = self.can_take(player, target - u8::from(won));
+            let depends = depends.and(played.decided_by);
+            self.take_back(played);
+            if reachable == maximizing {
+                result = reachable;
+                relevance = depends;
+                best = Some(card);
+                break;
+            }
+            relevance = relevance.and(depends);
+            settled = settled.or_below(card, depends);
+        }
+        // cancelled cards make any two cards of a suit matter
+        if self.duplicates == DuplicateCardRule::Cancel {
+            relevance = Relevance::ALL;
+        }
+        if trick_start {
+            let known = self.known.entry(
+                &self.descriptions[usize::from(tricks_left)],
+                relevance,
+                || Bounds {
+                    low: 0,
+                    high: tricks_left,
+                    best: None,
+                },
+            );
+            if result {
+                known.low = known.low.max(target);
+            } else {
+                known.high = known.high.min(target - 1);
+            }
+            known.best = best.or(known.best);
+        }
+        (result, relevance)
+    }
+
+    /// Whether the round can be played out so that every player takes exactly
+    /// `needed[player]` more tricks. When it cannot, also what that depends
+    /// on.
+    fn can_share(&mut self, needed: &mut [u8]) -> (bool, Relevance) {
+        let tricks_left = self.tricks_left();
+        if needed.iter().map(|tricks| u16::from(*tricks)).sum::<u16>() != u16::from(tricks_left) {
+            return (false, Relevance::LENGTHS);
+        }
+        if tricks_left == 0 {
+            return (true, Relevance::LENGTHS);
+        }
+        let trick_start = self.played == 0;
+        if trick_start {
+            // nobody can avoid the tricks their top trumps take, unless a
+            // joker beats them. A trump that beats them takes the trick, so
+            // nobody beats more of them than the tricks they still need,
+            // unless their trump is cancelled and lets a lower one win.
+            let jokers = self.hands.iter().any(|hand| hand.lengths[JOKER] > 0);
+            let cancels = self.duplicates == DuplicateCardRule::Cancel;
+            let players = self.players();
+            let most = |seat: usize| if cancels { u8::MAX } else { needed[seat] };
+            let forced = |seat: usize| {
+                let rivals = (0..players)
+                    .filter(move |other| *other != seat)
+                    .map(move |other| (other, most(other)));
+                self.trump_tricks(seat, rivals, needed[seat] + 1)
+            };
+            if !jokers {
+                let too_many = (0..players)
+                    .map(forced)
+                    .zip(needed.iter())
+                    .find(|((tricks, _), needed)| *tricks > **needed);
+                if let Some(((_, relevance), _)) = too_many {
+                    return (false, relevance);
+                }
+            }
+            self.describe().group.extend_from_slice(needed);
+            let description = &self.descriptions[usize::from(tricks_left)];
+            if let Some((_, relevance)) = self.dead_ends.get(description).next() {
+                return (false, relevance);
+            }
+        }
+        let mut relevance = Relevance::LENGTHS;
+        let mut settled = Relevance::ALL;
+        for card in self.sharing_moves(needed) {
+            if settled.covers(card) {
+                continue;
+            }
+            let played = self.play(card);
+            let (possible, depends) = match played.completed.map(|_| self.leader) {
+                Some(winner) if needed[winner] == 0 => (false, Relevance::LENGTHS),
+                Some(winner) => {
+                    needed[winner] -= 1;
+                    let possible = self.can_share(needed);
+                    needed[winner] += 1;
+                    possible
+                }
+                None => self.can_share(needed),
+            };
+            let depends = depends.and(played.decided_by);
+            self.take_back(played);
+            if possible {
+                return (true, Relevance::LENGTHS);
+            }
+            relevance = relevance.and(depends);
+            settled = settled.or_below(card, depends);
+        }
+        if self.duplicates == DuplicateCardRule::Cancel {
+            relevance = Relevance::ALL;
+        }
+        if trick_start {
+            let description = &self.descriptions[usize::from(tricks_left)];
+            self.dead_ends.entry(description, relevance, || ());
+        }
+        (false, relevance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use card_deck::standard_deck::{Card, Suit};
+
+    use crate::{
+        player::Player, trick_winner, DuplicateCardRule, GameConfig, InvalidPosition, Judgment,
+        Phase, RandomDriver, Transition,
+    };
+
+    use super::DoubleDummy;
+
+    /// The winner of the complete trick of `position`, as the engine decides
+    /// it.
+    fn trick_winner_of(position: &DoubleDummy) -> usize {
+        let players = position.hands.len();
+        let cards: Vec<(usize, Card)> = (0..players)
+            .map(|offset| (position.leader + offset) % players)
+            .map(|seat| (seat, position.trick[seat].unwrap()))
+            .collect();
+        trick_winner(&cards, position.trump_suit.as_ref(), position.duplicates).unwrap()
+    }
+
+    /// Whether the current player of `position` may play `card`, checked
+    /// with the engine.
+    fn follows(position: &DoubleDummy, card: &Card) -> bool {
+        let players = position.hands.len();
+        let played = position.trick.iter().filter(|card| card.is_some()).count();
+        let current = (position.leader + played) % players;
+        let led = (0..players)
+            .filter_map(|offset| position.trick[(position.leader + offset) % players])
+            .find(|card| !card.is_joker());
+        let mut player = Player::new();
+        player.assign(position.hands[current].iter().copied());
+        led.is_none_or(|led| player.follows_suit(card, &led))
+    }
+
+    /// Every way the rest of the round can end, as the tricks each player
+    /// takes, found by trying every sequence of legal cards.
+    fn outcomes(position: &DoubleDummy) -> Vec<Vec<u8>> {
+        let players = position.hands.len();
+        let played = position.trick.iter().filter(|card| card.is_some()).count();
+        let current = (position.leader + played) % players;
+        if position.hands[current].is_empty() {
+            return vec![vec![0; players]];
+        }
+        let hand = &position.hands[current];
+        let mut all = Vec::new();
+        for (index, card) in hand
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| follows(position, card))
+        {
+            let mut next = position.clone();
+            next.hands[current].remove(index);
+            next.trick[current] = Some(*card);
+            let mut winner = None;
+            if next.trick.iter().all(|card| card.is_some()) {
+                let best = trick_winner_of(&next);
+                next.trick = vec![None; players];
+                next.leader = best;
+                winner = Some(best);
+            }
+            for mut outcome in outcomes(&next) {
+                if let Some(winner) = winner {
+                    outcome[winner] += 1;
+                }
+                all.push(outcome);
+            }
+        }
+        all
+    }
+
+    /// The most tricks `player` can take, found by plain minimax.
+    fn brute_force_max(position: &DoubleDummy, player: usize) -> u8 {
+        let players = position.hands.len();
+        let played = position.trick.iter().filter(|card| card.is_some()).count();
+        let current = (position.leader + played) % players;
+        if position.hands[current].is_empty() {
+            return 0;
+        }
+        let hand = &position.hands[current];
+        let values = hand
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| follows(position, card))
+            .map(|(index, card)| {
+                let mut next = position.clone();
+                next.hands[current].remove(index);
+                next.trick[current] = Some(*card);
+                let mut won = 0;
+                if next.trick.iter().all(|card| card.is_some()) {
+                    let best = trick_winner_of(&next);
+                    next.trick = vec![None; players];
+                    next.leader = best;
+                    won = u8::from(best == player);
+                }
+                won + brute_force_max(&next, player)
+            });
+        if current == player {
+            values.max().unwrap()
+        } else {
+            values.min().unwrap()
+        }
+    }
+
+    /// Positions from random games, some of them in the middle of a trick.
+    fn random_positions(config: &GameConfig, count: u64) -> Vec<DoubleDummy> {
+        let players = config.players();
+        (0..count)
+            .map(|seed| {
+                let mut game = Judgment::with_config(config.clone());
+                game.start().unwrap();
+                let mut driver = RandomDriver::new(seed);
+                while game.phase() != Phase::Playing {
+                    let transition = driver.next_transition(&game).unwrap();
+                    game.update(transition).unwrap();
+                }
+                for _ in 0..seed % u64::from(players * 2) {
+                    let transition = driver.next_transition(&game).unwrap();
+                    game.update(transition).unwrap();
+                }
+                game.double_dummy().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_max_tricks_matches_brute_force() {
+        for (players, hand_size, decks) in [(2, 5, 1), (3, 4, 1), (4, 3, 1), (3, 4, 2)] {
+            let config = GameConfig::builder(players, hand_size)
+                .decks(decks)
+                .build()
+                .unwrap();
+            for position in random_positions(&config, 30) {
+                for player in 0..usize::from(players) {
+                    assert_eq!(
+                        position.max_tricks(player),
+                        brute_force_max(&position, player),
+                        "{position:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_all_take_matches_brute_force() {
+        for (players, hand_size, decks) in [(2, 5, 1), (3, 4, 1), (4, 3, 2)] {
+            let config = GameConfig::builder(players, hand_size)
+                .decks(decks)
+                .build()
+                .unwrap();
+            for position in random_positions(&config, 20) {
+                let possible = outcomes(&position);
+                let tricks_left = possible[0].iter().sum::<u8>();
+                // every way of sharing the remaining tricks
+                let mut shares = vec![vec![]];
+                for _ in 0..players {
+                    shares = shares
+                        .into_iter()
+                        .flat_map(|share: Vec<u8>| {
+                            (0..=tricks_left).map(move |tricks| {
+                                let mut share = share.clone();
+                                share.push(tricks);
+                                share
+                            })
+                        })
+                        .collect();
+                }
+                for share in shares {
+                    assert_eq!(
+                        position.can_all_take(&share),
+                        possible.contains(&share),
+                        "{share:?} in {position:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicate_rules_match_brute_force() {
+        for rule in [DuplicateCardRule::LastWins, DuplicateCardRule::Cancel] {
+            for (players, hand_size) in [(2, 5), (3, 4)] {
+                let config = GameConfig::builder(players, hand_size)
+                    .decks(2)
+                    .build()
+                    .unwrap();
+                for position in random_positions(&config, 20) {
+                    let position = position.with_duplicate_cards(rule);
+                    let possible = outcomes(&position);
+                    for player in 0..usize::from(players) {
+                        assert_eq!(
+                            position.max_tricks(player),
+                            brute_force_max(&position, player),
+                            "{position:?}"
+                        );
+                    }
+                    for outcome in &possible {
+                        assert!(
+                            position.can_all_take(outcome),
+                            "{outcome:?} in {position:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_jokers_match_brute_force() {
+        for rule in [
+            DuplicateCardRule::FirstWins,
+            DuplicateCardRule::LastWins,
+            DuplicateCardRule::Cancel,
+        ] {
+            for (players, hand_size) in [(2, 5), (3, 4)] {
+                // few cards in the deck make jokers common
+                let config = GameConfig::builder(players, hand_size)
+                    .jokers(2)
+                    .duplicate_cards(rule)
+                    .build()
+                    .unwrap();
+                for position in random_positions(&config, 20) {
+                    let possible = outcomes(&position);
+                    for player in 0..usize::from(players) {
+                        assert_eq!(
+                            position.max_tricks(player),
+                            brute_force_max(&position, player),
+                            "{position:?}"
+                        );
+                    }
+                    for outcome in &possible {
+                        assert!(
+                            position.can_all_take(outcome),
+                            "{outcome:?} in {position:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// A random full deal of four players, and the tricks they took when
+    /// the round was played out at random.
+    fn full_deal(seed: u64) -> (DoubleDummy, Vec<u8>) {
+        let config = GameConfig::builder(4, 13).build().unwrap();
+        let mut game = Judgment::with_config(config);
+        game.start().unwrap();
+        let mut driver = RandomDriver::new(seed);
+        while game.phase() != Phase::Playing {
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        let deal = game.double_dummy().unwrap();
+        while game.phase() == Phase::Playing {
+            let transition = driver.next_transition(&game).unwrap();
+            game.update(transition).unwrap();
+        }
+        (deal, game.round_results[0].tricks.clone())
+    }
+
+    /// Solve a full deal for every player, and check the answers agree.
+    fn solve_full_deal(deal: &DoubleDummy, taken: &[u8]) {
+        let tricks: Vec<u8> = (0..4).map(|player| deal.max_tricks(player)).collect();
+        // everyone else together can stop a player from taking more
+        assert!(tricks.iter().sum::<u8>() <= 13);
+        // the way the round went is one way to share the tricks
+        assert!(deal.can_all_take(taken));
+    }
+
+    #[test]
+    fn test_full_deal_is_solved() {
+        let (deal, taken) = full_deal(3);
+        solve_full_deal(&deal, &taken);
+    }
+
+    /// Timed, so only meaningful in an optimised build:
+    /// `cargo test --release -p judgment -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_full_deal_is_solved_quickly() {
+        for seed in 0..8 {
+            let (deal, taken) = full_deal(seed);
+            let start = std::time::Instant::now();
+            solve_full_deal(&deal, &taken);
+            assert!(
+                start.elapsed().as_secs() < 20,
+                "seed {seed} took {:?}",
+                start.elapsed()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_positions() {
+        let card = |suit| Card::new_normal(suit, card_deck::standard_deck::Rank::Ace);
+        let hands = vec![vec![card(Suit::Spades)], vec![card(Suit::Hearts)]];
+        assert_eq!(
+            DoubleDummy::new(hands.clone(), None, 0, vec![None]),
+            Err(InvalidPosition::PlayerCountMismatch)
+        );
+        assert_eq!(
+            DoubleDummy::new(hands.clone(), None, 2, vec![None, None]),
+            Err(InvalidPosition::LeaderOutOfRange)
+        );
+        assert_eq!(
+            DoubleDummy::new(hands.clone(), None, 0, vec![None, Some(card(Suit::Clubs))]),
+            Err(InvalidPosition::TrickOutOfOrder)
+        );
+        assert_eq!(
+            DoubleDummy::new(hands.clone(), None, 0, vec![Some(card(Suit::Clubs)), None]),
+            Err(InvalidPosition::UnevenHands)
+        );
+        let position = DoubleDummy::new(hands, Some(Suit::Hearts), 0, vec![None, None]).unwrap();
+        assert_eq!(position.max_tricks(0), 0);
+        assert_eq!(position.max_tricks(1), 1);
+        assert!(position.can_all_take(&[0, 1]));
+        assert!(!position.can_all_take(&[1, 0]));
+    }
+
+    #[test]
+    fn test_no_position_outside_play() {
+        let mut game = Judgment::new(3, 4, None);
+        assert_eq!(game.double_dummy(), None);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [0; 32] }).unwrap();
+        assert_eq!(game.double_dummy(), None);
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+/// How the players are split into teams, see
+/// [`GameConfigBuilder::teams`](crate::GameConfigBuilder::teams).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Teams {
+    /// The team of every seat, teams being numbered from 0.
+    seats: Vec<usize>,
+    bid: TeamBid,
+}
+
+impl Teams {
+    /// Put the player in every seat in the team `seats[seat]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use judgment::{TeamBid, Teams};
+    ///
+    /// // partners sit opposite each other
+    /// let teams = Teams::new(vec![0, 1, 0, 1], TeamBid::Sum);
+    /// assert_eq!(teams.members(1).collect::<Vec<_>>(), [1, 3]);
+    /// ```
+    pub fn new(seats: Vec<usize>, bid: TeamBid) -> Self {
+        Teams { seats, bid }
+    }
+
+    /// The team of every seat.
+    pub fn seats(&self) -> &[usize] {
+        &self.seats
+    }
+
+    pub fn bid(&self) -> TeamBid {
+        self.bid
+    }
+
+    /// The number of teams.
+    pub fn count(&self) -> usize {
+        self.seats.iter().max().map_or(0, |team| team + 1)
+    }
+
+    pub fn team_of(&self, seat: usize) -> usize {
+        self.seats[seat]
+    }
+
+    /// The seats of the players in `team`, in seating order.
+    pub fn members(&self, team: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.seats.len()).filter(move |seat| self.seats[*seat] == team)
+    }
+
+    /// The tricks of every team, given the tricks of every player.
+    pub fn tricks(&self, tricks: &[u8]) -> Vec<u8> {
+        let mut totals = vec![0; self.count()];
+        for (seat, tricks) in tricks.iter().enumerate() {
+            totals[self.seats[seat]] += tricks;
+        }
+        totals
+    }
+
+    /// The prediction of every team, given the prediction of every player.
+    /// With [`TeamBid::Joint`] all members hold the team's prediction.
+    pub fn predictions(&self, predictions: &[u8]) -> Vec<u8> {
+        match self.bid {
+            TeamBid::Sum => self.tricks(predictions),
+            TeamBid::Joint => (0..self.count())
+                .map(|team| {
+                    self.members(team)
+                        .next()
+                        .map_or(0, |seat| predictions[seat])
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Teams {
+    /// The seats of every team, e.g. `0 & 2 vs 1 & 3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let teams: Vec<String> = (0..self.count())
+            .map(|team| {
+                let members: Vec<String> =
+                    self.members(team).map(|seat| seat.to_string()).collect();
+                members.join(" & ")
+            })
+            .collect();
+        write!(f, "{}", teams.join(" vs "))
+    }
+}
+
+/// How a team arrives at its prediction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TeamBid {
+    /// Every player predicts as usual and the team's prediction is the sum.
+    #[default]
+    Sum,
+    /// The first member of a team to predict does so for the whole team, and
+    /// the others are skipped. Every member gains or loses the team's points.
+    Joint,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TeamBid, Teams};
+
+    #[test]
+    fn test_team_totals() {
+        let teams = Teams::new(vec![0, 1, 2, 0, 1, 2], TeamBid::Sum);
+        assert_eq!(teams.count(), 3);
+        assert_eq!(teams.tricks(&[1, 0, 2, 3, 0, 1]), [4, 0, 3]);
+        assert_eq!(teams.predictions(&[1, 0, 2, 1, 1, 1]), [2, 1, 3]);
+        assert_eq!(teams.to_string(), "0 & 3 vs 1 & 4 vs 2 & 5");
+        let teams = Teams::new(vec![1, 0, 1, 0], TeamBid::Joint);
+        assert_eq!(teams.predictions(&[3, 2, 3, 2]), [2, 3]);
+        assert_eq!(teams.tricks(&[1, 1, 1, 2]), [3, 2]);
+    }
+}
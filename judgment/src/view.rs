@@ -0,0 +1,186 @@
+use card_deck::standard_deck::{Card, Suit};
+
+use crate::{
+    BiddingMode, Claim, CompletedTrick, DuplicateCardRule, Judgment, Phase, Stage, Teams, Trick,
+};
+
+/// Everything one player is allowed to know about a game.
+///
+/// Other players' hands are reduced to the number of cards they hold, and
+/// their sealed predictions to whether they have made one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerView {
+    /// The player this view belongs to.
+    pub player: usize,
+    pub phase: Phase,
+    /// The player whose turn it is, if any.
+    pub current_player: Option<usize>,
+    /// The player who deals the current round, if any.
+    pub dealer: Option<usize>,
+    /// The player who leads the current trick, if any.
+    pub leader: Option<usize>,
+    /// Cards held by this player.
+    pub hand: Vec<Card>,
+    /// Number of cards held by every player.
+    pub hand_sizes: Vec<usize>,
+    /// Number of cards dealt to each player in the current round.
+    pub round_hand_size: Option<u8>,
+    /// Number of decks the cards are dealt from.
+    pub decks: u8,
+    /// Number of jokers in every deck.
+    pub jokers: u8,
+    /// How identical cards in a trick are settled.
+    pub duplicate_cards: DuplicateCardRule,
+    pub trump_suit: Option<Suit>,
+    /// The card turned up after the deal to choose the trump suit, if any.
+    pub turned_up: Option<Card>,
+    pub trick: Trick,
+    /// Tricks already played in the current round.
+    pub completed_tricks: Vec<CompletedTrick>,
+    /// The claim waiting for the claimant's opponents, if any.
+    pub claim: Option<Claim>,
+    /// How the players make their predictions.
+    pub bidding: BiddingMode,
+    /// What the points of a blind prediction are multiplied by, if they are
+    /// allowed.
+    pub blind_multiplier: Option<u8>,
+    /// Predictions of the current round that everyone knows; empty outside
+    /// of a round.
+    pub predictions: Vec<Option<u8>>,
+    /// This player's prediction, while it is still sealed.
+    pub sealed_prediction: Option<u8>,
+    /// Whether every player has predicted in the current round, sealed or
+    /// not; empty outside of a round.
+    pub predicted: Vec<bool>,
+    /// Whether every player predicted blind in the current round; empty
+    /// outside of a round.
+    pub blind: Vec<bool>,
+    /// Tricks won by every player in the current round; empty outside of a
+    /// round.
+    pub tricks_won: Vec<u8>,
+    pub scores: Vec<i64>,
+    /// The teams the players are split into, if they play in teams.
+    pub teams: Option<Teams>,
+    /// The score of every team; empty unless they play in teams.
+    pub team_scores: Vec<i64>,
+    /// Cards this player is allowed to play right now.
+    pub legal_cards: Vec<Card>,
+    /// Scores this player is allowed to predict right now.
+    pub legal_predictions: Vec<u8>,
+    /// Scores this player is allowed to predict blind right now.
+    pub legal_blind_predictions: Vec<u8>,
+}
+
+impl Judgment {
+    /// The game as seen by `player`, or `None` if there is no such player.
+    pub fn view_for(&self, player: usize) -> Option<PlayerView> {
+        let hand = self.hand_of_player(player)?.to_vec();
+        let round = match &self.stage {
+            Stage::PrePlay | Stage::Over => None,
+            Stage::Deal(round)
+            | Stage::PredictScores(round)
+            | Stage::Play(round)
+            | Stage::Claim(round, _) => Some(round),
+        };
+        Some(PlayerView {
+            player,
+            phase: self.phase(),
+            current_player: self.current_player(),
+            dealer: self.dealer(),
+            leader: self.leader(),
+            hand,
+            hand_sizes: self
+                .players
+                .iter()
+                .map(|player| player.hand().len())
+                .collect(),
+            round_hand_size: self.hand_size(),
+            decks: self.config.decks(),
+            jokers: self.config.jokers(),
+            duplicate_cards: self.config.duplicate_cards(),
+            trump_suit: self.trump_suit().cloned(),
+            turned_up: self.turned_up_card().copied(),
+            trick: self.trick.clone(),
+            completed_tricks: round
+                .map(|round| round.completed_tricks.clone())
+                .unwrap_or_default(),
+            claim: self.claim().cloned(),
+            bidding: self.config.bidding(),
+            blind_multiplier: self.config.blind_multiplier(),
+            predictions: round
+                .map(|round| round.predicted_scores.clone())
+                .unwrap_or_default(),
+            sealed_prediction: round.and_then(|round| round.sealed_scores[player]),
+            predicted: round
+                .map(|round| {
+                    (0..round.predicted_scores.len())
+                        .map(|player| round.has_predicted(player))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            blind: round.map(|round| round.blind.clone()).unwrap_or_default(),
+            tricks_won: round
+                .map(|round| round.trick_scores.clone())
+                .unwrap_or_default(),
+            scores: self.scores.clone(),
+            teams: self.config.teams().cloned(),
+            team_scores: self.team_scores.clone(),
+            legal_cards: self.legal_cards(player),
+            legal_predictions: self.legal_predictions(player),
+            legal_blind_predictions: self.legal_blind_predictions(player),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Judgment, Phase, Transition};
+
+    #[test]
+    fn test_view_hides_other_hands() {
+        let mut game = Judgment::new(3, 4, None);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [9; 32] }).unwrap();
+        for player in 0..3 {
+            let view = game.view_for(player).unwrap();
+            assert_eq!(view.hand, game.hand_of_player(player).unwrap());
+            assert_eq!(view.hand_sizes, [4, 4, 4]);
+            assert_eq!(view.phase, Phase::Predicting);
+            assert_eq!(view.current_player, Some(0));
+        }
+        assert!(game.view_for(3).is_none());
+    }
+
+    #[test]
+    fn test_view_tracks_completed_tricks() {
+        let mut game = Judgment::new(2, 3, None);
+        game.start().unwrap();
+        game.update(Transition::Deal { seed: [2; 32] }).unwrap();
+        game.update(Transition::PredictScore {
+            player: 0,
+            score: 1,
+        })
+        .unwrap();
+        game.update(Transition::PredictScore {
+            player: 1,
+            score: 1,
+        })
+        .unwrap();
+        let mut played = Vec::new();
+        for _ in 0..2 {
+            let player = game.current_player().unwrap();
+            let card = game.legal_cards(player)[0];
+            game.update(Transition::Play { player, card }).unwrap();
+            played.push((player, card));
+        }
+        let view = game.view_for(1).unwrap();
+        assert_eq!(view.completed_tricks.len(), 1);
+        let trick = &view.completed_tricks[0];
+        assert_eq!(trick.leader, 0);
+        assert_eq!(trick.cards, played);
+        assert_eq!(view.tricks_won[trick.winner], 1);
+        assert_eq!(view.hand_sizes, [2, 2]);
+        assert!(view.trick.iter().all(|card| card.is_none()));
+    }
+}